@@ -0,0 +1,107 @@
+use proc_macro2::{TokenStream, Ident};
+use quote::quote;
+use crate::internals::Build;
+use super::{Assemble, Entry};
+
+/// Builds by `new!`, which fills in the types of nested structs.
+impl Build for Assemble {
+    fn build(self) -> TokenStream {
+        let Assemble { ty, entries, defaults } = self;
+
+        let entries = entries.iter().map(|Entry { local, fields }| (local, fields.as_slice())).collect::<Vec<_>>();
+        let fields = build_fields(&entries, &mut Vec::new(), &defaults);
+        quote!(
+            ::kozo::new!(#ty { #fields })
+        )
+    }
+}
+
+/// `a: a, b: { c: x, .. }, ..` from the pairs of local variable and the rest of its field path
+/// from the struct at `prefix`
+fn build_fields(entries: &[(&Ident, &[Ident])], prefix: &mut Vec<Ident>, defaults: &[Vec<Ident>]) -> TokenStream {
+    let mut fields = Vec::<(&Ident, Vec<(&Ident, &[Ident])>)>::new();
+    for (local, path) in entries {
+        let (first, rest) = path.split_first().unwrap(/* a path has at least the root */);
+        match fields.iter_mut().find(|(field, _)| *field == first) {
+            Some((_, children)) => children.push((local, rest)),
+            None => fields.push((first, vec![(local, rest)])),
+        }
+    }
+
+    let default = defaults.contains(prefix).then(|| quote!(..::core::default::Default::default()));
+    let fields = fields.into_iter().map(|(field, children)| match children.as_slice() {
+        [(local, [])] => quote!(#field: #local),
+        children => {
+            prefix.push(field.clone());
+            let nested = build_fields(children, prefix, defaults);
+            prefix.pop();
+            quote!(#field: { #nested })
+        },
+    }).collect::<Vec<_>>();
+    quote!(#(#fields,)* #default)
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::{assemble::Assemble, Build};
+
+    #[test]
+    fn build_assemble() {
+        let case = parse2::<Assemble>(quote!(
+            a, x @ b.c, e @ f.e, b.d into Sample
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                ::kozo::new!(Sample { a: a, b: { c: x, d: d, }, f: { e: e, }, })
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_assemble_nested_default() {
+        let case = parse2::<Assemble>(quote!(
+            Sample { a, b: { c, .. } }
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                ::kozo::new!(Sample {
+                    a: a,
+                    b: { c: c, ..::core::default::Default::default() },
+                })
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_assemble_default() {
+        let case = parse2::<Assemble>(quote!(
+            Sample { a, b: { c }, .. }
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                ::kozo::new!(Sample {
+                    a: a,
+                    b: { c: c, },
+                    ..::core::default::Default::default()
+                })
+            ).to_string()
+        );
+
+        let case = parse2::<Assemble>(quote!(
+            a, b.c, .. into Sample
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                ::kozo::new!(Sample {
+                    a: a,
+                    b: { c: c, ..::core::default::Default::default() },
+                    ..::core::default::Default::default()
+                })
+            ).to_string()
+        )
+    }
+}
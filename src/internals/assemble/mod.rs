@@ -0,0 +1,20 @@
+use proc_macro2::Ident;
+
+mod parser;
+mod builder;
+
+
+/// `a, x @ b.c, b.d, .. into Sample`, or `Sample { a, b: { x @ c, d }, .. }`
+pub(super) struct Assemble {
+    ty:      syn::Path,
+    entries: Vec<Entry>,
+    /// Field paths of the structs whose other fields are filled by `Default::default()` ( `[]` for the outermost ) :
+    /// where `..` is written in the literal form, or every struct by trailing `..` in the list form
+    defaults: Vec<Vec<Ident>>,
+}
+
+/// A local variable and the path of field it's assembled into
+pub(super) struct Entry {
+    local:  Ident,
+    fields: Vec<Ident>,
+}
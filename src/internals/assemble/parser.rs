@@ -0,0 +1,187 @@
+use syn::{parse::{Parse, ParseStream}, token, braced};
+use super::*;
+use crate::internals::retrieve::Path;
+
+mod keyword {
+    syn::custom_keyword!(into);
+}
+
+impl Parse for Assemble {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let is_literal = {
+            let fork = input.fork();
+            fork.parse::<syn::Path>().is_ok() && fork.peek(token::Brace)
+        };
+
+        let (ty, entries, defaults) = if is_literal {
+            let ty = input.parse()?;
+            let (mut entries, mut defaults) = (Vec::new(), Vec::new());
+            parse_literal(input, &mut Vec::new(), &mut entries, &mut defaults)?;
+            (ty, entries, defaults)
+        } else {
+            let (mut entries, mut default) = (Vec::new(), false);
+            while !input.peek(keyword::into) {
+                if input.peek(token::Dot2) {
+                    input.parse::<token::Dot2>()?;
+                    default = true;
+                    break
+                }
+                entries.push(parse_entry(input)?);
+                if input.peek(keyword::into) {break}
+                input.parse::<token::Comma>()?;
+            }
+            input.parse::<keyword::into>()?;
+
+            let mut defaults = Vec::<Vec<Ident>>::new();
+            if default {
+                for entry in &entries {
+                    for depth in 0..entry.fields.len() {
+                        let prefix = entry.fields[..depth].to_vec();
+                        if !defaults.contains(&prefix) {defaults.push(prefix)}
+                    }
+                }
+            }
+            (input.parse()?, entries, defaults)
+        };
+
+        for (i, entry) in entries.iter().enumerate() {
+            let conflicting = entries[..i].iter().find(|other|
+                entry.fields.iter().zip(&other.fields).all(|(a, b)| a == b)
+            );
+            if let Some(other) = conflicting {
+                let path = |fields: &[Ident]| fields.iter().map(Ident::to_string).collect::<Vec<_>>().join(".");
+                let mut error = syn::Error::new(entry.local.span(), format!(
+                    "`{}` conflicts with `{}` assembled before", path(&entry.fields), path(&other.fields)
+                ));
+                error.combine(syn::Error::new(other.local.span(), "assembled here"));
+                return Err(error)
+            }
+        }
+
+        Ok(Self { ty, entries, defaults })
+    }
+}
+
+/// `x @ b.c` in the shared path grammar of `retrieve!`
+fn parse_entry(input: ParseStream) -> syn::Result<Entry> {
+    let rename = if input.peek2(token::At) {
+        let rename = input.parse::<Ident>()?;
+        input.parse::<token::At>()?;
+        Some(rename)
+    } else {
+        None
+    };
+    let path = input.fork().parse::<Path>()?;
+    let Some(fields) = path.field_names() else {
+        return Err(input.error("only named fields like `b.c` can be assembled"))
+    };
+    let fields = fields.into_iter().cloned().collect::<Vec<_>>();
+    input.parse::<Path>()?;
+
+    Ok(Entry {
+        local: rename.unwrap_or_else(|| fields.last().unwrap(/* a path has at least the root */).clone()),
+        fields,
+    })
+}
+
+/// `{ a, x @ b, c: { d }, .. }` with the fields of the parent `prefix`
+fn parse_literal(input: ParseStream, prefix: &mut Vec<Ident>, entries: &mut Vec<Entry>, defaults: &mut Vec<Vec<Ident>>) -> syn::Result<()> {
+    let fields_buf;
+    braced!(fields_buf in input);
+    while !fields_buf.is_empty() {
+        if fields_buf.peek(token::Dot2) {
+            fields_buf.parse::<token::Dot2>()?;
+            if !defaults.contains(prefix) {defaults.push(prefix.clone())}
+            if !fields_buf.is_empty() {
+                return Err(fields_buf.error("`..` must be at the end"))
+            }
+            break
+        }
+
+        if fields_buf.peek2(token::Colon) && !fields_buf.peek2(token::Colon2) {
+            prefix.push(fields_buf.parse()?);
+            fields_buf.parse::<token::Colon>()?;
+            parse_literal(&fields_buf, prefix, entries, defaults)?;
+            prefix.pop();
+        } else {
+            let rename = if fields_buf.peek2(token::At) {
+                let rename = fields_buf.parse::<Ident>()?;
+                fields_buf.parse::<token::At>()?;
+                Some(rename)
+            } else {
+                None
+            };
+            let field = fields_buf.parse::<Ident>()?;
+            entries.push(Entry {
+                local:  rename.unwrap_or_else(|| field.clone()),
+                fields: prefix.iter().cloned().chain([field]).collect(),
+            })
+        }
+
+        if fields_buf.is_empty() {break}
+        fields_buf.parse::<token::Comma>()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::Assemble;
+
+    fn entries(case: &Assemble) -> Vec<(String, String)> {
+        case.entries.iter().map(|entry| (
+            entry.local.to_string(),
+            entry.fields.iter().map(|f| f.to_string()).collect::<Vec<_>>().join("."),
+        )).collect()
+    }
+
+    fn defaults(case: &Assemble) -> Vec<String> {
+        case.defaults.iter().map(|prefix| prefix.iter().map(|f| f.to_string()).collect::<Vec<_>>().join(".")).collect()
+    }
+
+    #[test]
+    fn parse_assemble() {
+        let case = parse2::<Assemble>(quote!(
+            a, x @ b.c, b.d, .. into Sample
+        )).unwrap();
+        let ty = &case.ty;
+        assert_eq!(quote!(#ty).to_string(), "Sample");
+        assert_eq!(defaults(&case), ["", "b"]);
+        assert_eq!(entries(&case), [
+            ("a".into(), "a".into()), ("x".into(), "b.c".into()), ("d".into(), "b.d".into()),
+        ]);
+
+        let case = parse2::<Assemble>(quote!(
+            crate::Sample { a, b: { x @ c, d: { e } } }
+        )).unwrap();
+        assert!(case.defaults.is_empty());
+        assert_eq!(entries(&case), [
+            ("a".into(), "a".into()), ("x".into(), "b.c".into()), ("e".into(), "b.d.e".into()),
+        ]);
+
+        let case = parse2::<Assemble>(quote!(
+            Sample { a, b: { c, d: { e, .. } } }
+        )).unwrap();
+        assert_eq!(defaults(&case), ["b.d"]);
+    }
+    #[test]
+    fn parse_assemble_errors() {
+        assert!(parse2::<Assemble>(quote!(
+            a, b, x @ a into Sample
+        )).is_err());
+        assert!(parse2::<Assemble>(quote!(
+            b, b.c into Sample
+        )).is_err());
+        assert!(parse2::<Assemble>(quote!(
+            b?.c into Sample
+        )).is_err());
+        assert!(parse2::<Assemble>(quote!(
+            Sample { a, .., b: { c } }
+        )).is_err());
+        assert!(parse2::<Assemble>(quote!(
+            Sample { a, b: { c }, b: { d } }
+        )).is_ok(/* `b` is assembled from `c` and `d` */));
+    }
+}
@@ -0,0 +1,206 @@
+use proc_macro2::{TokenStream, TokenTree, Delimiter, Spacing, Span};
+use quote::{quote, format_ident, ToTokens};
+use syn::BinOp;
+use crate::internals::Build;
+use super::{AssertFields, Assertion, AssertionKind};
+
+/// Checks every assertion on a borrow of the target, and panics with all the failures together.
+impl Build for AssertFields {
+    fn build(self) -> TokenStream {
+        let target = &self.target;
+        let hidden = format_ident!("__kozo_target", span = Span::mixed_site());
+        let failures = format_ident!("__kozo_failures", span = Span::mixed_site());
+
+        let total = self.assertions.len();
+        let description = describe(target.to_token_stream());
+        let assertions = self.assertions.iter().map(|assertion| assertion.build(quote!(#hidden), &failures));
+        quote!({
+            let #hidden = &#target;
+            let mut #failures = ::std::vec::Vec::<::std::string::String>::new();
+            #( #assertions )*
+            if !#failures.is_empty() {
+                ::core::panic!("assertion failed for {} of {} fields of `{}`:\n{}",
+                    #failures.len(), #total, #description, #failures.join("\n")
+                )
+            }
+        })
+    }
+}
+
+impl Assertion {
+    /// Index steps out of bounds are also collected as a failure.
+    fn build(&self, base: TokenStream, failures: &syn::Ident) -> TokenStream {
+        let label = &self.label;
+        let out_of_bounds = quote!(
+            #failures.push(::std::format!("  {}: index out of bounds", #label));
+        );
+        self.path.build_checked_place(base, |value| self.build_check(value, failures), out_of_bounds)
+    }
+    fn build_check(&self, value: TokenStream, failures: &syn::Ident) -> TokenStream {
+        let label = &self.label;
+        let actual = format_ident!("__kozo_actual", span = Span::mixed_site());
+        match &self.kind {
+            AssertionKind::Compare { op, expected } => {
+                let expected_ident = format_ident!("__kozo_expected", span = Span::mixed_site());
+                let message = match op {
+                    BinOp::Ne(_) => "  {}: expected not {:?}, found {:?}",
+                    _            => "  {}: expected {:?}, found {:?}",
+                };
+                quote!(
+                    match (&#value, &#expected) {
+                        (#actual, #expected_ident) => if !(*#actual #op *#expected_ident) {
+                            #failures.push(::std::format!(#message, #label, #expected_ident, #actual));
+                        },
+                    }
+                )
+            },
+            AssertionKind::Matches { pattern, .. } => {
+                let description = describe(pattern.clone());
+                quote!(
+                    match &#value {
+                        #pattern => (),
+                        #actual => #failures.push(::std::format!(
+                            "  {}: expected to match {}, found {:?}", #label, #description, #actual
+                        )),
+                    }
+                )
+            },
+        }
+    }
+}
+
+/// Tokens as written in usual style like `F::Other { id: 1.., .. }` ( `to_string` gives `F :: Other { id : 1 .., .. }` )
+fn describe(tokens: TokenStream) -> String {
+    let mut description = String::new();
+    let mut is_word = false /* whether the last token is an ident or a literal */;
+    let mut op = String::new();
+    for token in tokens {
+        match token {
+            TokenTree::Punct(punct) => {
+                op.push(punct.as_char());
+                if punct.spacing() == Spacing::Joint {continue}
+                match op.as_str() {
+                    "," | ":" => description.push_str(&op),
+                    "|" | "@" | "=" | "=>" | "==" | "!=" | "<" | ">" | "<=" | ">=" | "&&" | "||" => {
+                        description.push_str(&format!(" {op}"))
+                    },
+                    _ => {
+                        description.push_str(&op);
+                        op.clear();
+                        is_word = false;
+                        continue
+                    },
+                }
+                description.push(' ');
+                op.clear();
+                is_word = false;
+            },
+            TokenTree::Group(group) => {
+                let inner = describe(group.stream());
+                match group.delimiter() {
+                    Delimiter::Brace       => description.push_str(&format!(" {{ {inner} }}")),
+                    Delimiter::Parenthesis => description.push_str(&format!("({inner})")),
+                    Delimiter::Bracket     => description.push_str(&format!("[{inner}]")),
+                    Delimiter::None        => description.push_str(&inner),
+                }
+                is_word = true;
+            },
+            word => {
+                if is_word {description.push(' ')}
+                description.push_str(&word.to_string());
+                is_word = true;
+            },
+        }
+    }
+    description.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::{quote, format_ident};
+    use proc_macro2::Span;
+    use syn::parse2;
+    use crate::internals::{assert_fields::AssertFields, Build};
+
+    #[test]
+    fn describe_pattern() {
+        assert_eq!(
+            super::describe(quote!(F::Other { id: 1.., .. } | F::X)),
+            "F::Other { id: 1.., .. } | F::X"
+        );
+        assert_eq!(
+            super::describe(quote!(Some(ref x) if x.len() >= 2)),
+            "Some(ref x) if x.len() >= 2"
+        );
+        assert_eq!(
+            super::describe(quote!([1, ..])),
+            "[1, ..]"
+        )
+    }
+    #[test]
+    fn build_assert_fields() {
+        let case = parse2::<AssertFields>(quote!(
+            s, b.c.d == 0, b.f matches F::X
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        let failures = format_ident!("__kozo_failures", span = Span::mixed_site());
+        let actual = format_ident!("__kozo_actual", span = Span::mixed_site());
+        let expected = format_ident!("__kozo_expected", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &s;
+                let mut #failures = ::std::vec::Vec::<::std::string::String>::new();
+                match (&#target.b.c.d, &0) {
+                    (#actual, #expected) => if !(*#actual == *#expected) {
+                        #failures.push(::std::format!("  {}: expected {:?}, found {:?}", "b.c.d", #expected, #actual));
+                    },
+                }
+                match &#target.b.f {
+                    F::X => (),
+                    #actual => #failures.push(::std::format!(
+                        "  {}: expected to match {}, found {:?}", "b.f", "F::X", #actual
+                    )),
+                }
+                if !#failures.is_empty() {
+                    ::core::panic!("assertion failed for {} of {} fields of `{}`:\n{}",
+                        #failures.len(), 2usize, "s", #failures.join("\n")
+                    )
+                }
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_assert_index() {
+        let case = parse2::<AssertFields>(quote!(
+            s, a[i].b == 1
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        let failures = format_ident!("__kozo_failures", span = Span::mixed_site());
+        let actual = format_ident!("__kozo_actual", span = Span::mixed_site());
+        let expected = format_ident!("__kozo_expected", span = Span::mixed_site());
+        let v = format_ident!("__kozo_v", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &s;
+                let mut #failures = ::std::vec::Vec::<::std::string::String>::new();
+                if let ::core::option::Option::Some(#v) = #target.a.get(i) {
+                    match (&(*#v).b, &1) {
+                        (#actual, #expected) => if !(*#actual == *#expected) {
+                            #failures.push(::std::format!("  {}: expected {:?}, found {:?}", "a[i].b", #expected, #actual));
+                        },
+                    }
+                } else {
+                    #failures.push(::std::format!("  {}: index out of bounds", "a[i].b"));
+                }
+                if !#failures.is_empty() {
+                    ::core::panic!("assertion failed for {} of {} fields of `{}`:\n{}",
+                        #failures.len(), 1usize, "s", #failures.join("\n")
+                    )
+                }
+            }).to_string()
+        )
+    }
+}
@@ -0,0 +1,38 @@
+use proc_macro2::TokenStream;
+use syn::{punctuated::Punctuated, token::Comma, Expr, BinOp};
+use super::retrieve::Path;
+
+mod parser;
+mod builder;
+
+
+mod keyword {
+    syn::custom_keyword!(matches);
+}
+
+/// `s, a == vec![1], b.c.d == 0, b.f matches F::X`
+pub(super) struct AssertFields {
+    target:     Expr,
+    _comma:     Comma,
+    assertions: Punctuated<Assertion, Comma>,
+}
+
+pub(super) struct Assertion {
+    path:  Path,
+    /// the path as written like `b.c.d`, shown in the failure message
+    label: String,
+    kind:  AssertionKind,
+}
+pub(super) enum AssertionKind {
+    /// `b.c.d == 0`, `a != vec![]`
+    Compare {
+        op:       BinOp,
+        expected: Box<Expr>,
+    },
+    /// `b.f matches F::Other { id: 1.., .. }`
+    Matches {
+        _matches: keyword::matches,
+        /// tokens to the next `,` ( may have `|` or `if` guard )
+        pattern:  TokenStream,
+    },
+}
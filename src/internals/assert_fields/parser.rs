@@ -0,0 +1,76 @@
+use proc_macro2::TokenTree;
+use syn::{parse::{Parse, ParseStream}, token};
+use super::*;
+
+impl Parse for AssertFields {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            target:     input.parse()?,
+            _comma:     input.parse()?,
+            assertions: input.parse_terminated(Assertion::parse)?,
+        })
+    }
+}
+
+impl Parse for Assertion {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let (path, label) = Path::parse_with_label(input)?;
+        if path.is_nullable() {
+            return Err(syn::Error::new(span, "`?` is not available in `assert_fields!`"))
+        }
+
+        let kind = if input.peek(keyword::matches) {
+            let _matches = input.parse()?;
+            let mut pattern = TokenStream::new();
+            while !input.is_empty() && !input.peek(token::Comma) {
+                pattern.extend([input.parse::<TokenTree>()?])
+            }
+            if pattern.is_empty() {
+                return Err(input.error("expected pattern"))
+            }
+            AssertionKind::Matches { _matches, pattern }
+        } else {
+            let op = input.parse::<BinOp>()
+                .ok().filter(|op| matches!(op, BinOp::Eq(_) | BinOp::Ne(_)))
+                .ok_or_else(|| input.error("expected `==`, `!=` or `matches`"))?;
+            AssertionKind::Compare { op, expected: input.parse()? }
+        };
+
+        Ok(Self { path, label, kind })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::{AssertFields, AssertionKind};
+
+    #[test]
+    fn parse_assert_fields() {
+        let case = parse2::<AssertFields>(quote!(
+            s, a == vec![1], b.c.d != 0, d[0] == 1, b.f matches F::Other { id: 1.., .. } | F::X, d.len() == 2,
+        )).unwrap();
+        assert_eq!(
+            case.assertions.iter().map(|a| a.label.as_str()).collect::<Vec<_>>(),
+            ["a", "b.c.d", "d[0]", "b.f", "d.len()"]
+        );
+        assert!(matches!(case.assertions[1].kind, AssertionKind::Compare { .. }));
+        let AssertionKind::Matches { pattern, .. } = &case.assertions[3].kind else {panic!()};
+        assert_eq!(pattern.to_string(), quote!(F::Other { id: 1.., .. } | F::X).to_string());
+    }
+    #[test]
+    fn parse_assert_fields_errors() {
+        assert!(parse2::<AssertFields>(quote!(
+            s, a < 1
+        )).is_err());
+        assert!(parse2::<AssertFields>(quote!(
+            s, b?.c == 1
+        )).is_err());
+        assert!(parse2::<AssertFields>(quote!(
+            s, b.f matches
+        )).is_err());
+    }
+}
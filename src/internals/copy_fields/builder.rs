@@ -0,0 +1,93 @@
+use proc_macro2::{TokenStream, TokenTree, Group, Span};
+use quote::quote;
+use crate::internals::Build;
+use crate::internals::retrieve::hoist_unless_place;
+use super::{CopyFields, Mode, Entry};
+
+/// Assigns each value to the destination field, from left to right.
+impl Build for CopyFields {
+    fn build(self) -> TokenStream {
+        let (mut statements, source) = hoist_unless_place(&self.source, "source");
+        let (dest_statement, dest) = hoist_unless_place(&self.dest, "dest");
+        statements.extend(dest_statement);
+
+        for Entry { span, path, dest: dest_path } in &self.entries {
+            let value = path.build_mut_place(relocate(source.clone(), *span), |place| place);
+            let value = self.mode.apply(value);
+            let place = match dest_path {
+                Some((_arrow, dest_path)) => dest_path.build_mut_place(dest.clone(), |place| place),
+                None => path.build_mut_place(dest.clone(), |place| place),
+            };
+            statements.extend(quote!(#place = #value;))
+        }
+        quote!({
+            #statements
+        })
+    }
+}
+
+impl Mode {
+    fn apply(&self, value: TokenStream) -> TokenStream {
+        let value = match self.clone {
+            None    => value,
+            Some(_) => quote!(::core::clone::Clone::clone(&#value)),
+        };
+        match self.into {
+            None    => value,
+            Some(_) => quote!(::core::convert::Into::into(#value)),
+        }
+    }
+}
+
+/// Moves `tokens` to `span` keeping their hygiene, so that a type mismatch of the value is reported at its path.
+fn relocate(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens.into_iter().map(|mut token| {
+        if let TokenTree::Group(group) = &token {
+            let mut relocated = Group::new(group.delimiter(), relocate(group.stream(), span));
+            relocated.set_span(group.span().located_at(span));
+            return TokenTree::Group(relocated)
+        }
+        token.set_span(token.span().located_at(span));
+        token
+    }).collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::{copy_fields::CopyFields, Build};
+
+    #[test]
+    fn build_copy_fields() {
+        let case = parse2::<CopyFields>(quote!(
+            a, b.c => x.c, d[0] => e from src to dst
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                dst.a = src.a;
+                dst.x.c = src.b.c;
+                dst.e = src.d[0];
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_copy_fields_cloned() {
+        let case = parse2::<CopyFields>(quote!(
+            clone into: a, b.c from &patch to &mut self.model
+        )).unwrap(/* this parsing passed in parser::test */);
+        let source = quote::format_ident!("__kozo_source", span = proc_macro2::Span::mixed_site());
+        let dest = quote::format_ident!("__kozo_dest", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #source = &patch;
+                let #dest = &mut self.model;
+                #dest.a = ::core::convert::Into::into(::core::clone::Clone::clone(&#source.a));
+                #dest.b.c = ::core::convert::Into::into(::core::clone::Clone::clone(&#source.b.c));
+            }).to_string()
+        )
+    }
+}
@@ -0,0 +1,40 @@
+use proc_macro2::Span;
+use syn::{punctuated::Punctuated, token::{Comma, Colon, FatArrow}, Expr};
+use super::retrieve::Path;
+
+mod parser;
+mod builder;
+
+
+mod keyword {
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(to);
+    syn::custom_keyword!(clone);
+    syn::custom_keyword!(into);
+}
+
+/// `a, b.c => x.c from src to dst`
+pub(super) struct CopyFields {
+    mode:    Mode,
+    entries: Punctuated<Entry, Comma>,
+    _from:   keyword::from,
+    source:  Expr,
+    _to:     keyword::to,
+    dest:    Expr,
+}
+
+/// `clone:`, `into:` or `clone into:` ( moving by default )
+#[derive(Default)]
+pub(super) struct Mode {
+    clone:  Option<keyword::clone>,
+    into:   Option<keyword::into>,
+    _colon: Option<Colon>,
+}
+
+/// `b.c => x.c`, or `a` for the same path in both
+pub(super) struct Entry {
+    /// where the value comes from, at which a type mismatch is reported
+    span: Span,
+    path: Path,
+    dest: Option<(FatArrow, Path)>,
+}
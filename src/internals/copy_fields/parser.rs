@@ -0,0 +1,115 @@
+use syn::parse::{Parse, ParseStream};
+use super::*;
+
+impl Parse for CopyFields {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mode = input.parse()?;
+
+        let mut entries = Punctuated::new();
+        while !input.peek(keyword::from) {
+            entries.push_value(input.parse()?);
+            if input.peek(keyword::from) {break}
+            entries.push_punct(input.parse()?);
+        }
+        if entries.is_empty() {
+            return Err(input.error("expected at least one field to copy"))
+        }
+
+        Ok(Self {
+            mode,
+            entries,
+            _from:  input.parse()?,
+            source: input.parse()?,
+            _to:    input.parse()?,
+            dest:   input.parse()?,
+        })
+    }
+}
+
+impl Parse for Mode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        let clone = fork.parse::<Option<keyword::clone>>()?;
+        let into = fork.parse::<Option<keyword::into>>()?;
+        if (clone.is_none() && into.is_none()) || !fork.peek(Colon) {
+            return Ok(Self::default())
+        }
+
+        Ok(Self {
+            clone:  input.parse()?,
+            into:   input.parse()?,
+            _colon: input.parse()?,
+        })
+    }
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let path = input.fork().parse::<Path>()?;
+        if path.is_nullable() {
+            return Err(input.error("`?` is not available in `copy_fields!`"))
+        }
+        input.parse::<Path>()?;
+
+        let dest = if input.peek(FatArrow) {
+            let arrow = input.parse()?;
+            let dest = input.fork().parse::<Path>()?;
+            if dest.is_nullable() || dest.ends_with_method() {
+                return Err(input.error("a destination must be a path of fields or indices like `x.c`"))
+            }
+            Some((arrow, input.parse()?))
+        } else {
+            if path.ends_with_method() {
+                return Err(input.error("a path ending with a method call needs its destination like `=> x.c`"))
+            }
+            None
+        };
+
+        Ok(Self { span, path, dest })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::CopyFields;
+
+    #[test]
+    fn parse_copy_fields() {
+        let case = parse2::<CopyFields>(quote!(
+            a, b.c => x.c from src to dst
+        )).unwrap();
+        assert!(case.mode.clone.is_none() && case.mode.into.is_none());
+        assert_eq!(case.entries.len(), 2);
+        assert!(case.entries[0].dest.is_none());
+        assert!(case.entries[1].dest.is_some());
+
+        let case = parse2::<CopyFields>(quote!(
+            clone into: a from &src to self.model
+        )).unwrap();
+        assert!(case.mode.clone.is_some() && case.mode.into.is_some());
+
+        let case = parse2::<CopyFields>(quote!(
+            into, clone from src to dst
+        )).unwrap();
+        assert!(case.mode.clone.is_none() && case.mode.into.is_none());
+    }
+    #[test]
+    fn parse_copy_fields_errors() {
+        assert!(parse2::<CopyFields>(quote!(
+            a from src
+        )).is_err());
+        assert!(parse2::<CopyFields>(quote!(
+            b?.c from src to dst
+        )).is_err());
+        assert!(parse2::<CopyFields>(quote!(
+            a => x.len() from src to dst
+        )).is_err());
+        assert!(parse2::<CopyFields>(quote!(
+            name.trim() from src to dst
+        )).is_err());
+    }
+}
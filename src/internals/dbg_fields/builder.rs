@@ -0,0 +1,96 @@
+use proc_macro2::{TokenStream, Span};
+use quote::{quote, format_ident};
+use crate::internals::Build;
+use crate::internals::retrieve::Borrow;
+use super::{DbgFields, TraceFields};
+
+/// Like `dbg!`, the target is moved into `match` to keep temporaries alive, and returned.
+impl Build for DbgFields {
+    fn build(self) -> TokenStream {
+        let target = &self.target;
+        let hidden = format_ident!("__kozo_target", span = Span::mixed_site());
+
+        let prints = self.fields.iter().map(|field| {
+            let label = &field.label;
+            let (value, _) = field.path.build_value(quote!(#hidden), 0, &Borrow::Ref(Default::default()));
+            match &self.logger {
+                None => quote!(
+                    ::std::eprintln!("[{}:{}:{}] {} = {:#?}",
+                        ::core::file!(), ::core::line!(), ::core::column!(), #label, #value
+                    );
+                ),
+                Some((logger, bang, _comma)) => quote!(
+                    #logger #bang("{} = {:?}", #label, #value);
+                ),
+            }
+        });
+        quote!(
+            match #target {
+                #hidden => {
+                    #( #prints )*
+                    #hidden
+                }
+            }
+        )
+    }
+}
+
+impl Build for TraceFields {
+    fn build(self) -> TokenStream {
+        self.0.build()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::{quote, format_ident};
+    use proc_macro2::Span;
+    use syn::parse2;
+    use crate::internals::{dbg_fields::{DbgFields, TraceFields}, Build};
+
+    #[test]
+    fn build_dbg_fields() {
+        let case = parse2::<DbgFields>(quote!(
+            s, a, b.c.d, d.len()
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                match s {
+                    #target => {
+                        ::std::eprintln!("[{}:{}:{}] {} = {:#?}",
+                            ::core::file!(), ::core::line!(), ::core::column!(), "a", &#target.a
+                        );
+                        ::std::eprintln!("[{}:{}:{}] {} = {:#?}",
+                            ::core::file!(), ::core::line!(), ::core::column!(), "b.c.d", &#target.b.c.d
+                        );
+                        ::std::eprintln!("[{}:{}:{}] {} = {:#?}",
+                            ::core::file!(), ::core::line!(), ::core::column!(), "d.len()", #target.d.len()
+                        );
+                        #target
+                    }
+                }
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_trace_fields() {
+        let case = parse2::<TraceFields>(quote!(
+            log::debug!, &s, b.c
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                match &s {
+                    #target => {
+                        log::debug!("{} = {:?}", "b.c", &#target.b.c);
+                        #target
+                    }
+                }
+            ).to_string()
+        )
+    }
+}
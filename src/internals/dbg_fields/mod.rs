@@ -0,0 +1,26 @@
+use syn::{punctuated::Punctuated, token::{Comma, Bang}, Expr};
+use super::retrieve::Path;
+
+mod parser;
+mod builder;
+
+
+/// `s, a, b.c.d, b?.f` : prints the fields with `file:line:column` like `dbg!`, and yields `s`
+pub(super) struct DbgFields {
+    /// `log::debug!,` at the beginning of `trace_fields!`
+    logger: Option<(syn::Path, Bang, Comma)>,
+    target: Expr,
+    _comma: Option<Comma>,
+    fields: Punctuated<Field, Comma>,
+}
+
+/// `log::debug!, s, a, b.c.d` : the same as `dbg_fields!` but through the logging macro
+pub(super) struct TraceFields(
+    DbgFields
+);
+
+pub(super) struct Field {
+    path:  Path,
+    /// the path as written like `b.c.d`, printed with the value
+    label: String,
+}
@@ -0,0 +1,74 @@
+use syn::{parse::{Parse, ParseStream}, token};
+use super::*;
+
+impl Parse for DbgFields {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let target = input.parse()?;
+        let _comma = input.parse::<Option<token::Comma>>()?;
+        let fields = if _comma.is_some() {
+            input.parse_terminated(Field::parse)?
+        } else {
+            Punctuated::new()
+        };
+        Ok(Self { logger: None, target, _comma, fields })
+    }
+}
+
+impl Parse for TraceFields {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let logger = (input.parse()?, input.parse()?, input.parse()?);
+        let mut dbg_fields = input.parse::<DbgFields>()?;
+        dbg_fields.logger = Some(logger);
+        Ok(Self(dbg_fields))
+    }
+}
+
+impl Parse for Field {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (path, label) = Path::parse_with_label(input)?;
+        if !input.is_empty() && !input.peek(token::Comma) {
+            return Err(input.error("expected `,`"))
+        }
+        Ok(Self { path, label })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::{DbgFields, TraceFields};
+
+    #[test]
+    fn parse_dbg_fields() {
+        let case = parse2::<DbgFields>(quote!(
+            s, a, b.c.d, b?.f, d[0], d.len(),
+        )).unwrap();
+        assert_eq!(
+            case.fields.iter().map(|f| f.label.as_str()).collect::<Vec<_>>(),
+            ["a", "b.c.d", "b?.f", "d[0]", "d.len()"]
+        );
+
+        let case = parse2::<DbgFields>(quote!(
+            &s
+        )).unwrap();
+        assert!(case.fields.is_empty());
+
+        assert!(parse2::<DbgFields>(quote!(
+            s, a == 1
+        )).is_err());
+    }
+    #[test]
+    fn parse_trace_fields() {
+        let TraceFields(case) = parse2::<TraceFields>(quote!(
+            log::debug!, s, a, b.c
+        )).unwrap();
+        assert!(case.logger.is_some());
+        assert_eq!(case.fields.len(), 2);
+
+        assert!(parse2::<TraceFields>(quote!(
+            log::debug, s, a
+        )).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, format_ident};
 use super::interpreter::{List, ContentType};
 use crate::internals::Build;
 
@@ -50,6 +50,37 @@ impl Build for List {
     }
 }
 
+impl List {
+    /// For each struct, emits a hidden macro that invokes
+    /// `::kozo::<callback>!{ <before> [<field name>: <type>, ..] <after> }`.
+    /// 
+    /// This enables macros like `retrieve!` to know the fields of a type defined by `define!`.
+    pub(crate) fn build_metadata(&self) -> TokenStream {
+        let mut result = TokenStream::new();
+
+        for def in &self.0 {
+            if let ContentType::Struct = def.content_type {
+                let metadata = format_ident!("__kozo_{}", def.name);
+                let fields = def.fields.iter().map(|field| {
+                    let (name, value_type) = (&field.name, &field.value_type);
+                    quote!(#name: #value_type)
+                });
+                result.extend(quote!(
+                    #[doc(hidden)]
+                    #[allow(unused_macros)]
+                    macro_rules! #metadata {
+                        ($callback:ident { $($before:tt)* } { $($after:tt)* }) => {
+                            ::kozo::$callback!{ $($before)* [#(#fields),*] $($after)* }
+                        };
+                    }
+                ))
+            }
+        }
+
+        result
+    }
+}
+
 
 #[cfg(test)]
 mod test {
@@ -115,6 +146,31 @@ mod test {
         )
     }
     #[test]
+    fn build_metadata() {
+        let case = parse2::<Define>(quote!(
+            struct Sample {
+                a: u8,
+                f: enum F {
+                    X,
+                    Y,
+                },
+            }
+        )).unwrap(/* this parsing passed in parser::test */)
+            .interpret(/* this interpreting passed in interpreter::test */);
+        assert_eq!(
+            case.build_metadata().to_string(),
+            quote!(
+                #[doc(hidden)]
+                #[allow(unused_macros)]
+                macro_rules! __kozo_Sample {
+                    ($callback:ident { $($before:tt)* } { $($after:tt)* }) => {
+                        ::kozo::$callback!{ $($before)* [a: u8, f: F] $($after)* }
+                    };
+                }
+            ).to_string()
+        )
+    }
+    #[test]
     fn build_double_nested_1() {
         let case = parse2::<Define>(quote!(
             struct NestedStruct {
@@ -12,53 +12,29 @@ mod partial_eq {
         let count = x.len();
         if count != y.len() {return false}
 
-        let mut buf = vec![];
-        for tx in x {buf.push(tx)}
-
-        let mut pos = 0;
-        for ty in y {
-            if ty != buf[pos] {return false}
-            pos += 1;
-        }
-        true
+        x.iter().zip(y.iter()).all(|(tx, ty)| tx == ty)
     }
     fn punctuated_types_eq(x: &Punctuated<Type, token::Comma>, y: &Punctuated<Type, token::Comma>) -> bool {
         let count = x.len();
         if count != y.len() {return false}
 
-        let mut buf = vec![];
-        for tx in x {buf.push(tx)}
-
-        let mut pos = 0;
-        for ty in y {
-            if !type_eq(ty, buf[pos]) {return false}
-            pos += 1;
-        }
-        true
+        x.iter().zip(y.iter()).all(|(tx, ty)| type_eq(tx, ty))
     }
     fn type_eq(x: &Type, y: &Type) -> bool {
         let (x, y) = (quote!(#x), quote!(#y));
         x.to_string() == y.to_string()
     }
-    fn eq_as_set<T: PartialEq + Clone>(x: &Vec<T>, y: &Vec<T>) -> bool {
-        let mut count = x.len();
-        if count != y.len() {return false}
+    fn eq_as_set<T: PartialEq + Clone>(x: &[T], y: &[T]) -> bool {
+        if x.len() != y.len() {return false}
 
-        let mut xindex = (0..count).collect::<Vec<_>>();
+        let mut xindex = (0..x.len()).collect::<Vec<_>>();
         for ty in y {
-            let mut found = false;
-            for i in 0..count {
-                if &x[xindex[i]] == ty {
-                    found = true;
-                    xindex.remove(i);
-                    count -= 1;
-                    if count == 0 {return true}
-                    break
-                }
+            match xindex.iter().position(|i| &x[*i] == ty) {
+                None      => return false,
+                Some(pos) => {xindex.remove(pos);},
             }
-            if !found {return false}
         }
-        false
+        xindex.is_empty()
     }
 
 
@@ -0,0 +1,149 @@
+use proc_macro2::{TokenStream, Span};
+use quote::{quote, format_ident};
+use syn::{parse::{Parser, ParseStream}, punctuated::Punctuated, Pat, Token};
+use crate::internals::Build;
+use super::{MatchesShape, Shape, Value};
+
+/// Matches every pattern in nested `match`es from left to right, so that
+/// the guard can use all the bindings.
+impl Build for MatchesShape {
+    fn build(self) -> TokenStream {
+        let target = &self.target;
+        let hidden = format_ident!("__kozo_target", span = Span::mixed_site());
+
+        let mut patterns = Vec::new();
+        self.shape.collect_patterns(quote!(#hidden), &mut patterns);
+
+        let mut body = match &self.guard {
+            Some((_if, guard)) if patterns.is_empty() => quote!(#guard),
+            _ => quote!(true),
+        };
+        for (i, (value, pattern)) in patterns.iter().rev().enumerate() {
+            let guard = self.guard.as_ref().filter(|_| i == 0).map(|(_if, guard)| quote!(#_if #guard));
+            body = quote!(
+                match #value {
+                    #pattern #guard => #body,
+                    _ => false,
+                }
+            )
+        }
+        quote!({
+            let #hidden = &#target;
+            #body
+        })
+    }
+}
+
+impl Shape {
+    /// Pairs of the borrowed value and its pattern
+    fn collect_patterns<'s>(&'s self, base: TokenStream, patterns: &mut Vec<(TokenStream, &'s TokenStream)>) {
+        for field in &self.fields {
+            let place = field.path.build_mut_place(base.clone(), |place| place);
+            match &field.value {
+                Value::Shape(shape) => shape.collect_patterns(place, patterns),
+                Value::Pattern(pattern) => {
+                    let value = match is_slice_pattern(pattern) {
+                        true  => quote!(&#place[..]) /* so that `[1, ..]` matches `Vec` too */,
+                        false => quote!(&#place),
+                    };
+                    patterns.push((value, pattern))
+                },
+            }
+        }
+    }
+}
+
+/// Whether `pattern` is `[..]`, also in `(..)`, `x @ ..` or any case of `.. | ..`
+fn is_slice_pattern(pattern: &TokenStream) -> bool {
+    fn is_slice(pat: &Pat) -> bool {
+        match pat {
+            Pat::Slice(_) => true,
+            Pat::Or(or) => or.cases.iter().any(is_slice),
+            Pat::Tuple(paren) if paren.elems.len() == 1 && !paren.elems.trailing_punct() /* `(..)` */ => {
+                is_slice(&paren.elems[0])
+            },
+            Pat::Ident(ident) => ident.subpat.as_ref().map(|(_at, pat)| is_slice(pat)).unwrap_or(false),
+            _ => false,
+        }
+    }
+    let cases = |input: ParseStream| {
+        input.parse::<Option<Token![|]>>()?;
+        Punctuated::<Pat, Token![|]>::parse_separated_nonempty(input)
+    };
+    match cases.parse2(pattern.clone()) {
+        Ok(cases) => cases.iter().any(is_slice),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use quote::{quote, format_ident};
+    use proc_macro2::Span;
+    use syn::parse2;
+    use crate::internals::{matches_shape::MatchesShape, Build};
+
+    #[test]
+    fn build_matches_shape() {
+        let case = parse2::<MatchesShape>(quote!(
+            s, { a: [1, ..], b: { c.d: 0 | 1, f: F::Other { id, .. }, .. } } if *id > 1
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &s;
+                match &#target.a[..] {
+                    [1, ..] => match &#target.b.c.d {
+                        0 | 1 => match &#target.b.f {
+                            F::Other { id, .. } if *id > 1 => true,
+                            _ => false,
+                        },
+                        _ => false,
+                    },
+                    _ => false,
+                }
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_matches_slice_cases() {
+        let case = parse2::<MatchesShape>(quote!(
+            s, { a: [_, ..] | [..], b: ([x, ..]), c: all @ [..] }
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &s;
+                match &#target.a[..] {
+                    [_, ..] | [..] => match &#target.b[..] {
+                        ([x, ..]) => match &#target.c[..] {
+                            all @ [..] => true,
+                            _ => false,
+                        },
+                        _ => false,
+                    },
+                    _ => false,
+                }
+            }).to_string()
+        );
+        assert!(super::is_slice_pattern(&quote!(([a, ..] | [..]))));
+        assert!(!super::is_slice_pattern(&quote!(([a, ..],))));
+        assert!(!super::is_slice_pattern(&quote!(Some(_) | None)))
+    }
+    #[test]
+    fn build_matches_shape_empty() {
+        let case = parse2::<MatchesShape>(quote!(
+            s.b, { .. }
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &s.b;
+                true
+            }).to_string()
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use syn::{punctuated::Punctuated, token::{Comma, Colon, Brace, Dot2, If}, Expr};
+use super::retrieve::Path;
+
+mod parser;
+mod builder;
+
+
+/// `s, { a: [1, ..], b: { f: F::Other { id, .. }, .. } } if *id > 1`
+pub(super) struct MatchesShape {
+    target: Expr,
+    _comma: Comma,
+    shape:  Shape,
+    /// evaluated with the bindings in the patterns
+    guard:  Option<(If, Expr)>,
+}
+
+/// `{ a: [1, ..], b.c: 0, .. }` : fields not listed are not checked ( `..` is allowed to say it explicitly )
+pub(super) struct Shape {
+    _brace: Brace,
+    fields: Punctuated<Field, Comma>,
+    _rest:  Option<Dot2>,
+}
+
+pub(super) struct Field {
+    path:   Path,
+    _colon: Colon,
+    value:  Value,
+}
+pub(super) enum Value {
+    /// `b: { f: F::X }`
+    Shape(Shape),
+    /// tokens to the next `,` ( may have `|` )
+    Pattern(TokenStream),
+}
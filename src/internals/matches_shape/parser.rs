@@ -0,0 +1,100 @@
+use proc_macro2::TokenTree;
+use syn::{parse::{Parse, ParseStream}, token, braced};
+use super::*;
+
+impl Parse for MatchesShape {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            target: input.parse()?,
+            _comma: input.parse()?,
+            shape:  input.parse()?,
+            guard:  if input.peek(token::If) {
+                Some((input.parse()?, input.parse()?))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl Parse for Shape {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fields_buf;
+        let _brace = braced!(fields_buf in input);
+
+        let (mut fields, mut _rest) = (Punctuated::new(), None);
+        while !fields_buf.is_empty() {
+            if fields_buf.peek(token::Dot2) {
+                _rest = Some(fields_buf.parse()?);
+                if !fields_buf.is_empty() {
+                    return Err(fields_buf.error("`..` must be at the end"))
+                }
+                break
+            }
+            fields.push_value(fields_buf.parse()?);
+            if fields_buf.is_empty() {break}
+            fields.push_punct(fields_buf.parse()?);
+        }
+
+        Ok(Self { _brace, fields, _rest })
+    }
+}
+
+impl Parse for Field {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.fork().parse::<Path>()?;
+        if path.is_nullable() {
+            return Err(input.error("`?` is not available in `matches_shape!` ( match `Some(..)` instead )"))
+        }
+        input.parse::<Path>()?;
+        let _colon = input.parse()?;
+
+        let value = if input.peek(token::Brace) {
+            Value::Shape(input.parse()?)
+        } else {
+            let mut pattern = TokenStream::new();
+            while !input.is_empty() && !input.peek(token::Comma) {
+                pattern.extend([input.parse::<TokenTree>()?])
+            }
+            if pattern.is_empty() {
+                return Err(input.error("expected pattern or nested `{ .. }`"))
+            }
+            Value::Pattern(pattern)
+        };
+
+        Ok(Self { path, _colon, value })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::{MatchesShape, Value};
+
+    #[test]
+    fn parse_matches_shape() {
+        let case = parse2::<MatchesShape>(quote!(
+            s, { a: [1, ..], b: { c.d: 0 | 1, f: F::Other { id, .. }, .. } } if *id > 1
+        )).unwrap();
+        assert!(case.guard.is_some());
+        assert_eq!(case.shape.fields.len(), 2);
+        let Value::Shape(b) = &case.shape.fields[1].value else {panic!()};
+        assert!(b._rest.is_some());
+        let Value::Pattern(pattern) = &b.fields[0].value else {panic!()};
+        assert_eq!(pattern.to_string(), quote!(0 | 1).to_string());
+    }
+    #[test]
+    fn parse_matches_shape_errors() {
+        assert!(parse2::<MatchesShape>(quote!(
+            s, { .., a: 0 }
+        )).is_err());
+        assert!(parse2::<MatchesShape>(quote!(
+            s, { a: }
+        )).is_err());
+        assert!(parse2::<MatchesShape>(quote!(
+            s, { b?.c: 0 }
+        )).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use proc_macro2::{Ident, TokenStream, Span};
+use quote::{quote, format_ident};
+use syn::{parse::{Parse, ParseStream}, punctuated::Punctuated, token::{Comma, Colon, Bracket}, bracketed, Type};
+use super::Build;
+
+
+/// `a: u8` in `[ ]` filled by the metadata macro `define!` emits
+pub(super) struct FieldMetadata {
+    pub(super) name: Ident,
+    _colon:          Colon,
+    pub(super) ty:   Type,
+}
+impl FieldMetadata {
+    /// `[a: u8, b: B]` if exists
+    pub(super) fn parse_list(input: ParseStream) -> syn::Result<Option<(Bracket, Punctuated<Self, Comma>)>> {
+        if input.peek(Bracket) {
+            let fields_buf;
+            Ok(Some((bracketed!(fields_buf in input), fields_buf.parse_terminated(Self::parse)?)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+impl Parse for FieldMetadata {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            name:   input.parse()?,
+            _colon: input.parse()?,
+            ty:     input.parse()?,
+        })
+    }
+}
+
+/// A macro invocation needing the fields of `ty` is deferred to the metadata macro of `ty`,
+/// which invokes `::kozo::<callback>!{ <before> [<fields>] <after> }`.
+pub(super) struct Deferred {
+    pub(super) callback: &'static str,
+    pub(super) ty:       Ident,
+    pub(super) before:   TokenStream,
+    pub(super) after:    TokenStream,
+}
+impl Build for Deferred {
+    fn build(self) -> TokenStream {
+        let Deferred { callback, ty, before, after } = self;
+        let metadata = format_ident!("__kozo_{}", ty, span = ty.span());
+        let callback = Ident::new(callback, Span::call_site());
+        quote!(
+            #metadata!{ #callback { #before } { #after } }
+        )
+    }
+}
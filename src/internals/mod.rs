@@ -9,14 +9,90 @@ trait Build {
 }
 
 
+mod metadata;
+
 mod define;
 pub(super) fn define(stream: TokenStream) -> Result<TokenStream> {
     use define::Define;
-    Ok(parse2::<Define>(stream)?.interpret().build())
+    let list = parse2::<Define>(stream)?.interpret();
+    let metadata = list.build_metadata();
+
+    let mut result = list.build();
+    result.extend(metadata);
+    Ok(result)
 }
 
 mod retrieve;
 pub(super) fn retrieve(stream: TokenStream) -> Result<TokenStream> {
     use retrieve::Retrieve;
     Ok(parse2::<Retrieve>(stream)?.build())
-}
\ No newline at end of file
+}
+pub(super) fn retrieving(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    use retrieve::Retrieving;
+    Ok(Retrieving::new(args, item)?.build())
+}
+
+mod new;
+pub(super) fn new(stream: TokenStream) -> Result<TokenStream> {
+    use new::New;
+    Ok(parse2::<New>(stream)?.build())
+}
+
+mod assemble;
+pub(super) fn assemble(stream: TokenStream) -> Result<TokenStream> {
+    use assemble::Assemble;
+    Ok(parse2::<Assemble>(stream)?.build())
+}
+
+mod update;
+pub(super) fn update(stream: TokenStream) -> Result<TokenStream> {
+    use update::Update;
+    Ok(parse2::<Update>(stream)?.build())
+}
+pub(super) fn with(stream: TokenStream) -> Result<TokenStream> {
+    use update::With;
+    Ok(parse2::<With>(stream)?.build())
+}
+mod take;
+pub(super) fn take(stream: TokenStream) -> Result<TokenStream> {
+    use take::Take;
+    Ok(parse2::<Take>(stream)?.build())
+}
+pub(super) fn replace(stream: TokenStream) -> Result<TokenStream> {
+    use take::Replace;
+    Ok(parse2::<Replace>(stream)?.build())
+}
+
+mod copy_fields;
+pub(super) fn copy_fields(stream: TokenStream) -> Result<TokenStream> {
+    use copy_fields::CopyFields;
+    Ok(parse2::<CopyFields>(stream)?.build())
+}
+
+mod assert_fields;
+pub(super) fn assert_fields(stream: TokenStream) -> Result<TokenStream> {
+    use assert_fields::AssertFields;
+    Ok(parse2::<AssertFields>(stream)?.build())
+}
+
+mod matches_shape;
+pub(super) fn matches_shape(stream: TokenStream) -> Result<TokenStream> {
+    use matches_shape::MatchesShape;
+    Ok(parse2::<MatchesShape>(stream)?.build())
+}
+
+mod dbg_fields;
+pub(super) fn dbg_fields(stream: TokenStream) -> Result<TokenStream> {
+    use dbg_fields::DbgFields;
+    Ok(parse2::<DbgFields>(stream)?.build())
+}
+pub(super) fn trace_fields(stream: TokenStream) -> Result<TokenStream> {
+    use dbg_fields::TraceFields;
+    Ok(parse2::<TraceFields>(stream)?.build())
+}
+
+mod path;
+pub(super) fn path(stream: TokenStream) -> Result<TokenStream> {
+    use path::Lens;
+    Ok(parse2::<Lens>(stream)?.build())
+}
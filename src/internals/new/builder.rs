@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::internals::Build;
+use super::{New, Field, Value};
+
+impl Build for New {
+    fn build(self) -> TokenStream {
+        if let Some(deferred) = self.deferred {
+            return deferred.build()
+        }
+
+        let New { path, brace, fields, base, .. } = self;
+        let fields = fields.iter().map(Field::build);
+        let base = base.map(|(dots, base)| quote!(#dots #base));
+
+        let mut literal = TokenStream::new();
+        brace.surround(&mut literal, |tokens| tokens.extend(quote!(
+            #(#fields,)* #base
+        )));
+        quote!(#path #literal)
+    }
+}
+
+impl Field {
+    fn build(&self) -> TokenStream {
+        let name = &self.name;
+        match &self.value {
+            None => quote!(#name),
+            Some((colon, Value::Expr(expr))) => quote!(#name #colon #expr),
+            Some((colon, Value::Nested { literal, ty })) => {
+                let ty = ty.as_ref().unwrap(/* filled by the metadata before building */);
+                quote!(#name #colon ::kozo::new!(#ty #literal))
+            },
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::{new::New, Build};
+
+    #[test]
+    fn build_new() {
+        let case = parse2::<New>(quote!(
+            Sample { a: vec![], b: { c: 0 } }
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                __kozo_Sample!{ new { Sample } { { a: vec![], b: { c: 0 } } } }
+            ).to_string()
+        );
+
+        let case = parse2::<New>(quote!(
+            Sample [a: Vec<u8>, b: B] { a: vec![], b: { c: 0, d }, ..base }
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                Sample { a: vec![], b: ::kozo::new!(B { c: 0, d }), ..base }
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_new_without_nested() {
+        let case = parse2::<New>(quote!(
+            Sample { a: vec![], b }
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                Sample { a: vec![], b, }
+            ).to_string()
+        )
+    }
+}
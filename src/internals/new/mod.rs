@@ -0,0 +1,33 @@
+use proc_macro2::{Ident, Group};
+use syn::{punctuated::Punctuated, token::{Comma, Colon, Bracket, Brace, Dot2}, Expr};
+use super::metadata::{FieldMetadata, Deferred};
+
+mod parser;
+mod builder;
+
+
+/// `NestedStruct { a: vec![], b: { c: { d: 0, e: 1 }, f: F::X } }`
+pub(super) struct New {
+    path:     syn::Path,
+    /// types of fields, filled by the metadata macro when some fields are nested
+    metadata: Option<(Bracket, Punctuated<FieldMetadata, Comma>)>,
+    brace:    Brace,
+    fields:   Punctuated<Field, Comma>,
+    base:     Option<(Dot2, Expr)>,
+    deferred: Option<Deferred>,
+}
+
+/// `a: vec![]`, `b: { .. }` or `a` ( shorthand )
+pub(super) struct Field {
+    name:  Ident,
+    value: Option<(Colon, Value)>,
+}
+
+pub(super) enum Value {
+    Expr(Expr),
+    /// `{ c: { d: 0, e: 1 }, f: F::X }` : built by another `new!` with the type of the field
+    Nested {
+        literal: Group,
+        ty:      Option<syn::Path>,
+    },
+}
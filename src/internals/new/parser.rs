@@ -0,0 +1,142 @@
+use proc_macro2::{TokenTree, Delimiter, Spacing};
+use quote::quote;
+use syn::{parse::{Parse, ParseStream}, punctuated::Punctuated, token, braced, Type};
+use super::*;
+
+impl Parse for New {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path     = input.parse::<syn::Path>()?;
+        let metadata = FieldMetadata::parse_list(input)?;
+        let literal  = input.fork().parse::<TokenTree>()?;
+
+        let fields_buf;
+        let brace = braced!(fields_buf in input);
+        let mut fields = Punctuated::new();
+        while !fields_buf.is_empty() && !fields_buf.peek(token::Dot2) {
+            fields.push_value(fields_buf.parse::<Field>()?);
+            if fields_buf.is_empty() {break}
+            fields.push_punct(fields_buf.parse()?);
+        }
+        let base = if fields_buf.peek(token::Dot2) {
+            Some((fields_buf.parse()?, fields_buf.parse()?))
+        } else {
+            None
+        };
+
+        let mut new = Self { path, metadata, brace, fields, base, deferred: None };
+
+        let has_nested = new.fields.iter().any(|field| matches!(field.value, Some((_, Value::Nested { .. }))));
+        match &new.metadata {
+            None => if has_nested {
+                let path = &new.path;
+                new.deferred = Some(Deferred {
+                    callback: "new",
+                    ty:       path.segments.last().unwrap(/* `syn::Path` has at least 1 segment */).ident.clone(),
+                    before:   quote!(#path),
+                    after:    quote!(#literal),
+                })
+            },
+            Some((_bracket, metadata)) => for field in &mut new.fields {
+                let Some((_colon, Value::Nested { literal, ty })) = &mut field.value else {continue};
+                let name = &field.name;
+                let Some(field_metadata) = metadata.iter().find(|m| m.name == *name) else {
+                    let path = &new.path;
+                    return Err(syn::Error::new(name.span(), format!(
+                        "no field `{name}` in `{}`", quote!(#path).to_string().replace(' ', "")
+                    )))
+                };
+                match &field_metadata.ty {
+                    Type::Path(syn::TypePath { qself: None, path })
+                    if path.segments.iter().all(|segment| segment.arguments.is_empty()) => {
+                        /* for errors like missing fields to point to the literal */
+                        let mut path = path.clone();
+                        for segment in &mut path.segments {
+                            segment.ident.set_span(literal.span())
+                        }
+                        *ty = Some(path)
+                    },
+                    _ => return Err(syn::Error::new(name.span(), format!(
+                        "`{name}` is not a struct to build by `{{ .. }}`"
+                    ))),
+                }
+            },
+        }
+
+        Ok(new)
+    }
+}
+
+impl Parse for Field {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            name:  input.parse()?,
+            value: if input.peek(token::Colon) {
+                Some((input.parse()?, input.parse()?))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if is_nested_literal(input) {
+            let TokenTree::Group(literal) = input.parse::<TokenTree>()? else {unreachable!(/* checked in is_nested_literal */)};
+            Ok(Self::Nested { literal, ty: None })
+        } else {
+            Ok(Self::Expr(input.parse()?))
+        }
+    }
+}
+
+/// `{ c: .., }` or `{ c, d }` is a nested literal, but `{ c }` is a block.
+fn is_nested_literal(input: ParseStream) -> bool {
+    let Ok(TokenTree::Group(group)) = input.fork().parse::<TokenTree>() else {return false};
+    if group.delimiter() != Delimiter::Brace {
+        return false
+    }
+    let mut tokens = group.stream().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(punct))) =>
+            (punct.as_char() == ':' && punct.spacing() == Spacing::Alone) || punct.as_char() == ',',
+        _ => false,
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::{New, Value};
+
+    #[test]
+    fn parse_new() {
+        let case = parse2::<New>(quote!(
+            Sample { a: vec![], b: { c: { d: 0 }, f }, g: { 1 }, ..Default::default() }
+        )).unwrap();
+        assert_eq!(case.fields.len(), 3);
+        assert!(matches!(case.fields[1].value, Some((_, Value::Nested { .. }))));
+        assert!(matches!(case.fields[2].value, Some((_, Value::Expr(_)))));
+        assert!(case.base.is_some());
+        let deferred = case.deferred.unwrap();
+        assert_eq!(deferred.before.to_string(), quote!(Sample).to_string());
+        assert_eq!(deferred.after.to_string(), quote!({ a: vec![], b: { c: { d: 0 }, f }, g: { 1 }, ..Default::default() }).to_string());
+
+        let case = parse2::<New>(quote!(
+            Sample [a: Vec<u8>, b: B] { a: vec![], b: { c: 0 } }
+        )).unwrap();
+        assert!(case.deferred.is_none());
+        assert!(matches!(&case.fields[1].value, Some((_, Value::Nested { ty: Some(_), .. }))));
+    }
+    #[test]
+    fn parse_new_errors() {
+        assert!(parse2::<New>(quote!(
+            Sample [a: Vec<u8>, b: B] { x: { c: 0 } }
+        )).is_err());
+        assert!(parse2::<New>(quote!(
+            Sample [a: Vec<u8>, b: B] { a: { c: 0 } }
+        )).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use proc_macro2::{TokenStream, Span};
+use quote::{quote, format_ident};
+use crate::internals::Build;
+use super::Lens;
+
+/// A zero-sized value of a type defined in place, having `get` / `get_mut` / `set` of the field.
+impl Build for Lens {
+    fn build(self) -> TokenStream {
+        if let Some(deferred) = self.deferred {
+            return deferred.build()
+        }
+
+        let Lens { root, fields, ty, .. } = self;
+        let ty = ty.unwrap(/* filled by the metadata before building */);
+        let lens = format_ident!("__kozo_Lens", span = Span::mixed_site());
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        let value = format_ident!("__kozo_value", span = Span::mixed_site());
+        let fields = fields.iter().map(|(_dot, name)| quote!(.#name)).collect::<TokenStream>();
+        quote!({
+            #[allow(non_camel_case_types)]
+            #[derive(Clone, Copy)]
+            struct #lens;
+            #[allow(dead_code)]
+            impl #lens {
+                fn get(self, #target: &#root) -> &#ty {
+                    &#target #fields
+                }
+                fn get_mut(self, #target: &mut #root) -> &mut #ty {
+                    &mut #target #fields
+                }
+                fn set(self, #target: &mut #root, #value: #ty) {
+                    #target #fields = #value;
+                }
+            }
+            #lens
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::{quote, format_ident};
+    use proc_macro2::Span;
+    use syn::parse2;
+    use crate::internals::{path::Lens, Build};
+
+    #[test]
+    fn build_lens() {
+        let case = parse2::<Lens>(quote!(
+            [a: u8, b: B] [c: Vec<u8>] Sample.b.c
+        )).unwrap(/* this parsing passed in parser::test */);
+        let lens = format_ident!("__kozo_Lens", span = Span::mixed_site());
+        let target = format_ident!("__kozo_target", span = Span::mixed_site());
+        let value = format_ident!("__kozo_value", span = Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                #[allow(non_camel_case_types)]
+                #[derive(Clone, Copy)]
+                struct #lens;
+                #[allow(dead_code)]
+                impl #lens {
+                    fn get(self, #target: &Sample) -> &Vec<u8> {
+                        &#target.b.c
+                    }
+                    fn get_mut(self, #target: &mut Sample) -> &mut Vec<u8> {
+                        &mut #target.b.c
+                    }
+                    fn set(self, #target: &mut Sample, #value: Vec<u8>) {
+                        #target.b.c = #value;
+                    }
+                }
+                #lens
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_deferred() {
+        let case = parse2::<Lens>(quote!(
+            [a: u8, b: B] Sample.b.c
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                __kozo_B!{ path { [a: u8, b: B] } { Sample.b.c } }
+            ).to_string()
+        )
+    }
+}
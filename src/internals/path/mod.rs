@@ -0,0 +1,20 @@
+use proc_macro2::Ident;
+use syn::{token::Dot, Type};
+use super::metadata::Deferred;
+
+mod parser;
+mod builder;
+
+
+/// `Sample.b.c.d` : a lens to `b.c.d` of `Sample`
+/// 
+/// Fields of `Sample`, `B` and `C` are filled in `[ ]`s before the path, one by one,
+/// by the metadata macros `define!` emits.
+pub(super) struct Lens {
+    root:     Ident,
+    fields:   Vec<(Dot, Ident)>,
+    /// the type of the last field, known by the metadata of its owner
+    ty:       Option<Type>,
+    /// when the fields of the next type are not filled yet
+    deferred: Option<Deferred>,
+}
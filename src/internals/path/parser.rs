@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream;
+use syn::{parse::{Parse, ParseStream}, Type};
+use super::*;
+use crate::internals::metadata::FieldMetadata;
+
+impl Parse for Lens {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let source = input.fork().parse::<TokenStream>()?;
+
+        let mut lists = Vec::new();
+        while let Some((_bracket, list)) = FieldMetadata::parse_list(input)? {
+            lists.push(list)
+        }
+        let rest = input.fork().parse::<TokenStream>()?;
+
+        let root = input.parse::<Ident>()?;
+        let mut fields = Vec::new();
+        while !input.is_empty() {
+            fields.push((input.parse()?, input.parse()?))
+        }
+        if fields.is_empty() {
+            return Err(syn::Error::new(root.span(), "expected some fields like `Sample.a`"))
+        }
+
+        let mut owner = root.clone();
+        for (i, (_dot, name)) in fields.iter().enumerate() {
+            let Some(list) = lists.get(i) else {
+                let source = source.into_iter().collect::<Vec<_>>();
+                let end_of_metadata = source.len() - rest.into_iter().count();
+                let deferred = Deferred {
+                    callback: "path",
+                    ty:       owner,
+                    before:   source[..end_of_metadata].iter().cloned().collect(),
+                    after:    source[end_of_metadata..].iter().cloned().collect(),
+                };
+                return Ok(Self { root, fields, ty: None, deferred: Some(deferred) })
+            };
+            let Some(field) = list.iter().find(|field| field.name == *name) else {
+                return Err(syn::Error::new(name.span(), format!("no field `{name}` in `{owner}`")))
+            };
+            if i + 1 == fields.len() {
+                let ty = Some(field.ty.clone());
+                return Ok(Self { root, fields, ty, deferred: None })
+            }
+            owner = match &field.ty {
+                Type::Path(ty) if ty.qself.is_none() && ty.path.segments.last().map(|s| s.arguments.is_empty()).unwrap_or(false) => {
+                    ty.path.segments.last().unwrap(/* checked above */).ident.clone()
+                },
+                _ => return Err(syn::Error::new(name.span(), format!(
+                    "`{name}` is not a struct defined by `define!`"
+                ))),
+            };
+        }
+        unreachable!(/* returned at the last field */)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::Lens;
+
+    #[test]
+    fn parse_lens() {
+        let case = parse2::<Lens>(quote!(
+            Sample.b.c
+        )).unwrap();
+        let deferred = case.deferred.unwrap();
+        assert_eq!(deferred.ty.to_string(), "Sample");
+        assert!(deferred.before.is_empty());
+        assert_eq!(deferred.after.to_string(), quote!(Sample.b.c).to_string());
+
+        let case = parse2::<Lens>(quote!(
+            [a: u8, b: crate::B] Sample.b.c
+        )).unwrap();
+        let deferred = case.deferred.unwrap();
+        assert_eq!(deferred.ty.to_string(), "B");
+        assert_eq!(deferred.before.to_string(), quote!([a: u8, b: crate::B]).to_string());
+
+        let case = parse2::<Lens>(quote!(
+            [a: u8, b: crate::B] [c: Vec<u8>] Sample.b.c
+        )).unwrap();
+        assert!(case.deferred.is_none());
+        assert_eq!(case.ty.map(|ty| quote!(#ty).to_string()), Some(quote!(Vec<u8>).to_string()));
+    }
+    #[test]
+    fn parse_lens_errors() {
+        assert!(parse2::<Lens>(quote!(
+            Sample
+        )).is_err());
+        assert!(parse2::<Lens>(quote!(
+            [a: u8] Sample.x
+        )).is_err());
+        assert!(parse2::<Lens>(quote!(
+            [a: Vec<u8>] Sample.a.len
+        )).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use proc_macro2::{TokenStream, TokenTree, Span};
+use quote::quote;
+use syn::{parse2, FnArg, ItemFn, Pat, Type};
+use crate::internals::Build;
+use super::{Retrieve, Retrieving};
+
+impl Retrieving {
+    pub fn new(args: TokenStream, item: TokenStream) -> syn::Result<Self> {
+        let function = parse2::<ItemFn>(item)?;
+
+        let args = if has_from(&args) {
+            args
+        } else {
+            let target = first_parameter(&function)?;
+            quote!(#args from #target)
+        };
+        let retrieve = parse2::<Retrieve>(args)?;
+        if retrieve.expression.is_some() || retrieve.into.is_some() {
+            return Err(syn::Error::new(Span::call_site(),
+                "`=>` or `into ..` is not available in `#[retrieving(..)]`"
+            ))
+        }
+
+        Ok(Self { retrieve, function })
+    }
+}
+
+/// Whether `from` appears as the keyword, not in a path like `String::from`
+fn has_from(args: &TokenStream) -> bool {
+    let mut after_path_separator = false;
+    for token in args.clone() {
+        match &token {
+            TokenTree::Ident(ident) if ident == "from" && !after_path_separator => return true,
+            TokenTree::Punct(punct) => after_path_separator = matches!(punct.as_char(), ':' | '.'),
+            _ => after_path_separator = false,
+        }
+    }
+    false
+}
+
+/// The first parameter as a target, borrowed when it's a reference
+fn first_parameter(function: &ItemFn) -> syn::Result<TokenStream> {
+    let Some(first) = function.sig.inputs.first() else {
+        return Err(syn::Error::new_spanned(&function.sig,
+            "`#[retrieving(..)]` without `from ..` needs a parameter to retrieve from"
+        ))
+    };
+    match first {
+        FnArg::Receiver(receiver) => {
+            let self_token = &receiver.self_token;
+            Ok(match &receiver.reference {
+                None => quote!(#self_token),
+                Some(_) => match &receiver.mutability {
+                    None       => quote!(&#self_token),
+                    Some(mut_) => quote!(&#mut_ #self_token),
+                },
+            })
+        },
+        FnArg::Typed(typed) => {
+            let Pat::Ident(pat) = &*typed.pat else {
+                return Err(syn::Error::new_spanned(&typed.pat,
+                    "`#[retrieving(..)]` without `from ..` needs the first parameter to be a name like `req: Request`"
+                ))
+            };
+            let name = &pat.ident;
+            Ok(match &*typed.ty {
+                Type::Reference(reference) => match &reference.mutability {
+                    None       => quote!(&#name),
+                    Some(mut_) => quote!(&#mut_ #name),
+                },
+                _ => quote!(#name),
+            })
+        },
+    }
+}
+
+impl Build for Retrieving {
+    fn build(self) -> TokenStream {
+        let Self { retrieve, function } = self;
+        let ItemFn { attrs, vis, sig, block } = function;
+
+        let retrieval = retrieve.build();
+        let stmts = &block.stmts;
+        quote!(
+            #(#attrs)*
+            #vis #sig {
+                #retrieval
+                #(#stmts)*
+            }
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use crate::internals::{retrieve::Retrieving, Build};
+
+    #[test]
+    fn build_retrieving() {
+        let case = Retrieving::new(
+            quote!(a, d @ b.c),
+            quote!(fn handle(req: Request) -> u8 { a + d }),
+        ).unwrap();
+        assert_eq!(
+            case.build().to_string(),
+            quote!(fn handle(req: Request) -> u8 {
+                let a = req.a;
+                let d = req.b.c;
+                a + d
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_retrieving_borrowed() {
+        let case = Retrieving::new(
+            quote!(a, len @ b => Vec::from(b).len()),
+            quote!(pub fn len(&self) -> usize { len }),
+        ).unwrap();
+        assert_eq!(
+            case.build().to_string(),
+            quote!(pub fn len(&self) -> usize {
+                let a = &self.a;
+                let len = {
+                    let b = &self.b;
+                    Vec::from(b).len()
+                };
+                len
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_retrieving_from() {
+        let case = Retrieving::new(
+            quote!(clone: name from user),
+            quote!(fn greet(id: u64, user: User) -> User { println!("{name}"); user }),
+        ).unwrap();
+        assert_eq!(
+            case.build().to_string(),
+            quote!(fn greet(id: u64, user: User) -> User {
+                let name = (&user.name).clone();
+                println!("{name}");
+                user
+            }).to_string()
+        )
+    }
+    #[test]
+    fn new_retrieving_errors() {
+        assert!(Retrieving::new(quote!(a), quote!(fn f() {})).is_err());
+        assert!(Retrieving::new(quote!(a), quote!(fn f((a, b): (u8, u8)) {})).is_err());
+        assert!(Retrieving::new(quote!(=> a), quote!(fn f(s: S) {})).is_err());
+    }
+}
@@ -0,0 +1,1036 @@
+use std::iter;
+use proc_macro2::{TokenStream, Ident};
+use quote::{quote, format_ident, ToTokens};
+use syn::{Expr, Member, Block, punctuated::Punctuated, token::Comma, parse_quote};
+use crate::internals::Build;
+use super::{Retrieve, Mode, Group, Entry, Nested, Assignee, Transform, Path, StepKind, Nullable, Target, Borrow, Pattern, is_place, build_tuple, hidden_ident};
+
+
+/// What is common to all entries of a `retrieve!`
+struct Context<'r> {
+    borrow:     &'r Borrow,
+    fallback:   Option<&'r Block>,
+    cloning:    bool,
+    assigning:  bool,
+    collecting: bool,
+    exhaustive: bool,
+}
+
+impl Build for Retrieve {
+    fn build(self) -> TokenStream {
+        if let Some(deferred) = self.deferred {
+            return deferred.build()
+        }
+
+        let cloning   = matches!(self.mode, Some(Mode::Clone { .. }));
+        let assigning = matches!(self.mode, Some(Mode::Assign { .. }));
+
+        let bindings = self.bindings();
+        let statements = self.groups.into_iter()
+            .map(|group| group.build(cloning, assigning))
+            .collect::<TokenStream>();
+
+        if let Some((_into, path)) = self.into {
+            return quote!({
+                #statements
+                #path { #(#bindings),* }
+            })
+        }
+        match self.expression {
+            None => statements,
+            Some(_) => {
+                let value = build_tuple(&bindings);
+                quote!({
+                    #statements
+                    #value
+                })
+            },
+        }
+    }
+}
+
+impl Group {
+    fn build(self, cloning: bool, assigning: bool) -> TokenStream {
+        let mut target = self.target;
+        if cloning {
+            if let Borrow::Move = target.borrow {
+                target.borrow = Borrow::Ref(Default::default())
+            }
+        }
+
+        let context = Context {
+            borrow:     &target.borrow,
+            fallback:   self.fallback.as_ref().map(|(_else, block)| block),
+            cloning,
+            assigning,
+            collecting: self.each.is_some(),
+            exhaustive: self.exhaustive.is_some(),
+        };
+        if self.each.is_none() {
+            return build_statements(&self.entries, self.pattern.as_ref(), &target, &context)
+        }
+
+        /* `each`: retrieves from every item in a single loop and collects them */
+        let item = hidden_ident("item");
+        let item_target = Target {
+            borrow: target.borrow.clone(),
+            expr:   match target.borrow {
+                Borrow::Move => parse_quote!(#item),
+                _            => parse_quote!((*#item)),
+            },
+        };
+        let item_statements = build_statements(&self.entries, self.pattern.as_ref(), &item_target, &context);
+
+        let bindings = self.entries.iter().map(Entry::binding).collect::<Vec<_>>();
+        let values = bindings.iter().map(hidden_ident).collect::<Vec<_>>();
+        let collections = self.entries.iter().map(|entry| match &entry.ascribed {
+            Some((_colon, ty)) => quote!(#ty),
+            None => quote!(::std::vec::Vec<_>),
+        });
+        let items = target.borrow.apply(target.to_token_stream());
+
+        quote!(
+            #( let mut #bindings: #collections = ::core::default::Default::default(); )*
+            for #item in #items {
+                let (#(#values,)*) = {
+                    #item_statements
+                    (#(#bindings,)*)
+                };
+                #( ::core::iter::Extend::extend(&mut #bindings, ::core::iter::once(#values)); )*
+            }
+        )
+    }
+}
+
+fn build_statements(entries: &Punctuated<Entry, Comma>, pattern: Option<&Pattern>, target: &Target, context: &Context) -> TokenStream {
+    match pattern {
+        None => build_projections(entries, target, context),
+        Some(pattern) => pattern.build_statements(entries, target, context),
+    }
+}
+
+/// Entries are evaluated from left to right.
+/// 
+/// A target that is not a place ( with the borrow of target ), and method calls shared by
+/// the paths of some entries before any `?` ( as they return ), are evaluated once and bound to
+/// hidden variables before the first use. Later steps from there are borrowed as usual.
+fn build_projections(entries: &Punctuated<Entry, Comma>, target: &Target, context: &Context) -> TokenStream {
+    let mut statements = TokenStream::new();
+
+    let base = if entries.len() > 1 && !is_place(&target.expr) {
+        let hidden = hidden_ident("target");
+        let value = context.borrow.apply(target.to_token_stream());
+        statements.extend(quote!(let #hidden = #value;));
+        quote!(#hidden)
+    } else {
+        target.to_token_stream()
+    };
+
+    let mut receivers: Vec<(String, Ident)> = Vec::new();
+    for entry in entries {
+        let steps = iter::once(&entry.path.root).chain(&entry.path.rest).collect::<Vec<_>>();
+
+        let (mut skipped, mut receiver) = (0, base.clone());
+        for (len, key) in entry.path.calls() {
+            if entries.iter().filter(|e| e.path.calls().iter().any(|(_, k)| *k == key)).count() < 2 {
+                continue
+            }
+            let hoisted = match receivers.iter().find(|(k, _)| *k == key) {
+                Some((_, hoisted)) => hoisted.clone(),
+                None => {
+                    let StepKind::Method { method, .. } = &steps[len - 1].kind else {
+                        unreachable!(/* calls end with a method */)
+                    };
+                    let hoisted = if receivers.iter().any(|(_, r)| *r == hidden_ident(method)) {
+                        hidden_ident(format_ident!("{}_{}", method, receivers.len()))
+                    } else {
+                        hidden_ident(method)
+                    };
+                    let suffixes = steps[skipped..len].iter().map(|step| step.kind.to_suffix());
+                    statements.extend(quote!(let #hoisted = #receiver #(#suffixes)*;));
+                    receivers.push((key, hoisted.clone()));
+                    hoisted
+                },
+            };
+            (skipped, receiver) = (len, quote!(#hoisted));
+        }
+        statements.extend(entry.build_statement(receiver, skipped, context))
+    }
+
+    statements
+}
+
+impl Entry {
+    /// `skipped` is the number of steps already applied to `base`
+    /// ( the root field destructured by pattern, or method calls evaluated once ).
+    fn build_statement(&self, base: TokenStream, skipped: usize, context: &Context) -> TokenStream {
+        let (value, is_optional) = self.build_value(base, skipped, context);
+
+        let (unwrapping, value) = match (is_optional, context.fallback) {
+            (true, Some(fallback)) => {
+                let unwrapped = if context.assigning {hidden_ident("v")} else {self.binding()};
+                (
+                    Some(quote!(let ::core::option::Option::Some(#unwrapped) = #value else #fallback;)),
+                    self.apply_transform(quote!(#unwrapped)),
+                )
+            },
+            (true, None) => (None, match &self.transform {
+                Some(Transform::Map { _arrow, expr }) => {
+                    let name = self.transform_input();
+                    quote!(#value.map(|#name| #expr))
+                },
+                Some(Transform::Clone { .. } | Transform::Copy { .. }) | None => value,
+                Some(_) => {
+                    let v = hidden_ident("v");
+                    let transformed = self.apply_transform(quote!(#v));
+                    quote!(#value.map(|#v| #transformed))
+                },
+            }),
+            (false, _) => (None, self.apply_transform(value)),
+        };
+
+        if context.assigning {
+            let assignment = match &self.assignee {
+                None => {
+                    let binding = self.binding();
+                    quote!(#binding = #value;)
+                },
+                Some(Assignee::Arrow { _arrow, place }) => quote!(
+                    #place = #value;
+                ),
+                Some(Assignee::Compound { place, op }) => quote!(
+                    #place #op #value;
+                ),
+            };
+            quote!(#unwrapping #assignment)
+        } else {
+            let binding = self.binding();
+            let ascribed = self.ascribed.as_ref()
+                .filter(|_| !context.collecting /* then it's the type of collection */)
+                .map(|(colon, ty)| quote!(#colon #ty));
+            let is_converted = ascribed.is_some() || !matches!(self.transform,
+                None | Some(Transform::Clone { .. } | Transform::Copy { .. })
+            );
+            match unwrapping {
+                Some(unwrapping) if !is_converted => unwrapping,
+                _ => quote!(
+                    #unwrapping
+                    let #binding #ascribed = #value;
+                ),
+            }
+        }
+    }
+
+    /// `clone` / `copy` retrieves the value through a borrow,
+    /// so that the target is still available after that.
+    fn build_value(&self, base: TokenStream, skipped: usize, context: &Context) -> (TokenStream, bool) {
+        let is_copy = matches!(self.transform, Some(Transform::Copy { .. }));
+        let is_clone = !is_copy && (context.cloning || matches!(self.transform, Some(Transform::Clone { .. })));
+
+        let borrow = match context.borrow {
+            Borrow::Move if is_copy || is_clone => &Borrow::Ref(Default::default()),
+            borrow => borrow,
+        };
+        if let Some((_eq, default)) = &self.default {
+            return (self.build_defaulted(base, skipped, borrow, default, is_clone, is_copy), false)
+        }
+        let (value, is_optional) = self.path.build_value(base, skipped, borrow);
+
+        let value = match (is_clone, is_copy, is_optional) {
+            (true, _, true)  => quote!(#value.cloned()),
+            (true, _, false) => quote!((#value).clone()),
+            (_, true, true)  => quote!(#value.copied()),
+            (_, true, false) => quote!(*#value),
+            (false, false, _) => value,
+        };
+        (value, is_optional)
+    }
+
+    /// `Option` value is unwrapped by `.unwrap_or_else(|| default)`.
+    /// 
+    /// When it's borrowed, the result is `Cow` ( or owned value by `clone` / `copy` ),
+    /// and with `&mut` target, the default is inserted to the field by `.get_or_insert_with`.
+    fn build_defaulted(&self, base: TokenStream, skipped: usize, borrow: &Borrow, default: &Expr, is_clone: bool, is_copy: bool) -> TokenStream {
+        let option = if self.path.is_nullable() || self.path.ends_with_method() {
+            self.path.build_value(base, skipped, borrow).0
+        } else {
+            let (place, _) = self.path.build_value(base, skipped, &Borrow::Move);
+            match borrow {
+                Borrow::Move   => place,
+                Borrow::Ref(_) => quote!(#place.as_ref()),
+                Borrow::Mut(..) => {
+                    let value = quote!(#place.get_or_insert_with(|| #default));
+                    return match (is_clone, is_copy) {
+                        (true, _) => quote!((#value).clone()),
+                        (_, true) => quote!(*#value),
+                        _ => value,
+                    }
+                },
+            }
+        };
+
+        let v = hidden_ident("v");
+        let is_borrowed = !matches!(borrow, Borrow::Move) && !self.path.ends_with_method();
+        match (is_clone, is_copy, is_borrowed) {
+            (true, _, _) => quote!(#option.cloned().unwrap_or_else(|| #default)),
+            (_, true, _) => quote!(#option.copied().unwrap_or_else(|| #default)),
+            (false, false, true) => quote!(
+                match #option {
+                    ::core::option::Option::Some(#v) => ::std::borrow::Cow::Borrowed(#v),
+                    ::core::option::Option::None => ::std::borrow::Cow::Owned(#default),
+                }
+            ),
+            (false, false, false) => quote!(#option.unwrap_or_else(|| #default)),
+        }
+    }
+
+    fn apply_transform(&self, value: TokenStream) -> TokenStream {
+        match &self.transform {
+            None => value,
+            Some(Transform::As { _as, ty }) => quote!(
+                (#value) #_as #ty
+            ),
+            Some(Transform::Into { _into, ty }) => quote!(
+                ::core::convert::Into::<#ty>::into(#value)
+            ),
+            Some(Transform::Clone { .. } | Transform::Copy { .. }) => value,
+            Some(Transform::Map { _arrow, expr }) => {
+                let name = self.transform_input();
+                quote!({
+                    let #name = #value;
+                    #expr
+                })
+            },
+        }
+    }
+    /// The name by which `=> ..` refers to the retrieved value :
+    /// the last field name, or the binding name for tuple field.
+    fn transform_input(&self) -> Ident {
+        match self.path.last() {
+            Member::Named(ident) => ident,
+            Member::Unnamed(_) => self.binding(),
+        }
+    }
+}
+
+impl Path {
+    /// Returns the value and whether it's wrapped in `Option` by nullable steps.
+    /// 
+    /// Steps after the first nullable one are applied in `.map` / `.and_then`.
+    /// A nullable index step is retrieved by `.get` ( `.get_mut` ), and moving
+    /// out of the element is replaced by borrowing from there.
+    /// What a method call returns is taken as it is, not borrowed.
+    pub fn build_value(&self, base: TokenStream, skipped: usize, borrow: &Borrow) -> (TokenStream, bool) {
+        let v = hidden_ident("v");
+        let mut borrow = borrow.clone();
+
+        let (mut value, mut is_optional, mut suffixes) = (base, false, Vec::new());
+        for (i, step) in iter::once(&self.root).chain(&self.rest).enumerate() {
+            let place = |suffixes: &[TokenStream]| if is_optional {
+                quote!(#v #(#suffixes)*)
+            } else {
+                quote!(#value #(#suffixes)*)
+            };
+            let option = match (&step.kind, &step.nullable) {
+                (StepKind::Index { index, .. }, Some(nullable)) => {
+                    let place = place(&suffixes);
+                    let get = match borrow {
+                        Borrow::Mut(..) => quote!(get_mut),
+                        _ => quote!(get),
+                    };
+                    if let Borrow::Move = borrow {
+                        borrow = Borrow::Ref(Default::default())
+                    }
+                    Some(nullable.map_result(quote!(#place.#get(#index))))
+                }
+                (kind @ StepKind::Method { .. }, Some(nullable)) if i >= skipped => {
+                    suffixes.push(kind.to_suffix());
+                    Some(nullable.map_result(place(&suffixes)))
+                }
+                (kind, nullable) => {
+                    if i >= skipped {
+                        suffixes.push(kind.to_suffix())
+                    }
+                    nullable.as_ref().map(|nullable| nullable.build_option(place(&suffixes), &borrow))
+                }
+            };
+            if let Some(option) = option {
+                value = if is_optional {
+                    quote!(#value.and_then(|#v| #option))
+                } else {
+                    option
+                };
+                is_optional = true;
+                suffixes.clear()
+            }
+        }
+        if !suffixes.is_empty() {
+            if let (Borrow::Move, Some(StepKind::Index { index, .. })) = (&borrow, self.rest.last().map(|step| &step.kind)) {
+                if matches!(**index, Expr::Range(_)) {
+                    borrow = Borrow::Ref(Default::default())
+                }
+            }
+            if self.ends_with_method() {
+                borrow = Borrow::Move
+            }
+            value = if is_optional {
+                let place = borrow.apply(quote!(#v #(#suffixes)*));
+                quote!(#value.map(|#v| #place))
+            } else {
+                borrow.apply(quote!(#value #(#suffixes)*))
+            }
+        }
+        (value, is_optional)
+    }
+}
+
+impl Path {
+    /// `(length, tokens)` of the prefixes ending with a method call, before any nullable step
+    pub(super) fn calls(&self) -> Vec<(usize, String)> {
+        let mut tokens = TokenStream::new();
+        iter::once(&self.root).chain(&self.rest)
+            .take_while(|step| step.nullable.is_none())
+            .enumerate()
+            .filter_map(|(i, step)| {
+                tokens.extend(step.kind.to_suffix());
+                matches!(step.kind, StepKind::Method { .. }).then(|| (i + 1, tokens.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl StepKind {
+    fn to_suffix(&self) -> TokenStream {
+        match self {
+            StepKind::Field { member, .. } => quote!(.#member),
+            StepKind::Index { index, .. }  => quote!([#index]),
+            StepKind::Method { method, args, .. } => quote!(.#method(#args)),
+        }
+    }
+}
+
+impl Path {
+    /// `body` with the place of this path from `base`, in nested `if let Some(..) = ..`
+    /// for nullable steps ( used by `update!` ).
+    pub fn build_mut_place(&self, base: TokenStream, body: impl FnOnce(TokenStream) -> TokenStream) -> TokenStream {
+        let v = hidden_ident("v");
+
+        let (mut place, mut scrutinees) = (base, Vec::new());
+        for step in iter::once(&self.root).chain(&self.rest) {
+            let option = match (&step.kind, &step.nullable) {
+                (StepKind::Index { index, .. }, Some(nullable)) => nullable.map_result(quote!(#place.get_mut(#index))),
+                (kind @ StepKind::Method { .. }, Some(nullable)) => {
+                    let suffix = kind.to_suffix();
+                    nullable.map_result(quote!(#place #suffix))
+                },
+                (kind, Some(nullable)) => {
+                    let suffix = kind.to_suffix();
+                    nullable.build_option(quote!(#place #suffix), &Borrow::Mut(Default::default(), Default::default()))
+                },
+                (kind, None) => {
+                    let suffix = kind.to_suffix();
+                    place = quote!(#place #suffix);
+                    continue
+                },
+            };
+            scrutinees.push(option);
+            place = quote!((*#v));
+        }
+
+        scrutinees.into_iter().rev().fold(body(place), |body, option| quote!(
+            if let ::core::option::Option::Some(#v) = #option {
+                #body
+            }
+        ))
+    }
+}
+
+impl Path {
+    /// `body` with the place of this path from `base`, where index steps are taken by `.get(..)`
+    /// in nested `if let Some(..) = ..` and `otherwise` runs when one is out of bounds ( used by `assert_fields!` ).
+    pub fn build_checked_place(&self, base: TokenStream, body: impl FnOnce(TokenStream) -> TokenStream, otherwise: TokenStream) -> TokenStream {
+        let v = hidden_ident("v");
+
+        let (mut place, mut scrutinees) = (base, Vec::new());
+        for step in iter::once(&self.root).chain(&self.rest) {
+            match &step.kind {
+                StepKind::Index { index, .. } => {
+                    scrutinees.push(quote!(#place.get(#index)));
+                    place = quote!((*#v));
+                },
+                kind => {
+                    let suffix = kind.to_suffix();
+                    place = quote!(#place #suffix);
+                },
+            }
+        }
+
+        scrutinees.into_iter().rev().fold(body(place), |body, option| quote!(
+            if let ::core::option::Option::Some(#v) = #option {
+                #body
+            } else {
+                #otherwise
+            }
+        ))
+    }
+}
+
+impl Nullable {
+    fn build_option(&self, place: TokenStream, borrow: &Borrow) -> TokenStream {
+        let option = match borrow {
+            Borrow::Move      => place,
+            Borrow::Ref(_)    => quote!(#place.as_ref()),
+            Borrow::Mut(_, _) => quote!(#place.as_mut()),
+        };
+        self.map_result(option)
+    }
+    fn map_result(&self, option: TokenStream) -> TokenStream {
+        match self {
+            Nullable::Option { .. } => option,
+            Nullable::Result { .. } => quote!(#option.ok()),
+        }
+    }
+}
+
+impl Borrow {
+    fn apply(&self, place: TokenStream) -> TokenStream {
+        match self {
+            Borrow::Move           => place,
+            Borrow::Ref(and)       => quote!(#and #place),
+            Borrow::Mut(and, mut_) => quote!(#and #mut_ #place),
+        }
+    }
+}
+
+impl Pattern {
+    /// Destructures the target by `let <pattern> = <target> else { .. };`.
+    /// Entries having nested or nullable path are retrieved from the (hidden) binding of their root field.
+    /// 
+    /// From a moved target, a root field only `clone`d or `copy`ed is bound by `ref`, so that the target is still available.
+    fn build_statements(&self, entries: &Punctuated<Entry, Comma>, target: &Target, context: &Context) -> TokenStream {
+        let mut fields: Vec<(&Member, TokenStream)> = Vec::new();
+        let mut projections = TokenStream::new();
+
+        for entry in entries {
+            let root = entry.path.root_member();
+            let is_shared = entries.iter().filter(|e| e.path.root_member() == root).count() > 1;
+            let is_borrowed = matches!(target.borrow, Borrow::Move) && entries.iter()
+                .filter(|e| e.path.root_member() == root)
+                .all(|e| matches!(e.transform, Some(Transform::Clone { .. } | Transform::Copy { .. })));
+
+            if let Some(nested) = &entry.nested {
+                let hidden = hidden_ident(root);
+                fields.push((root, quote!(#hidden)));
+                projections.extend(nested.build_statement(quote!(#hidden), context));
+                continue
+            }
+            if entry.path.rest.is_empty() && entry.path.root.nullable.is_none()
+            && entry.ascribed.is_none() && entry.default.is_none() && entry.transform.is_none()
+            && !context.cloning && !context.assigning && !is_shared {
+                let binding = entry.binding();
+                fields.push((root, quote!(#binding)))
+            } else {
+                let hidden = hidden_ident(root);
+                let ref_token = is_borrowed.then(|| quote!(ref));
+                if !fields.iter().any(|(member, _)| *member == root) {
+                    fields.push((root, quote!(#ref_token #hidden)))
+                }
+                projections.extend(
+                    entry.build_statement(quote!(#hidden), 1, context)
+                )
+            }
+        }
+
+        let path = &self.path;
+        let pattern = match fields.first() {
+            Some((Member::Unnamed(_), _)) => {
+                let mut positions = Vec::new();
+                for (member, binding) in fields {
+                    let Member::Unnamed(index) = member else {unreachable!(/* rejected in parser */)};
+                    let index = index.index as usize;
+                    if positions.len() <= index {
+                        positions.resize(index + 1, quote!(_))
+                    }
+                    positions[index] = binding
+                }
+                let rest = (!context.exhaustive).then(|| quote!(..));
+                quote!(#path(#(#positions,)* #rest))
+            },
+            _ => {
+                let fields = fields.into_iter().map(|(member, binding)|
+                    if quote!(#member).to_string() == binding.to_string() {
+                        binding
+                    } else {
+                        quote!(#member: #binding)
+                    }
+                );
+                let rest = (!context.exhaustive).then(|| quote!(..));
+                quote!(#path { #(#fields,)* #rest })
+            },
+        };
+        let scrutinee = target.borrow.apply(target.to_token_stream());
+        let fallback = context.fallback.map(|block| quote!(else #block));
+
+        quote!(
+            let #pattern = #scrutinee #fallback;
+            #projections
+        )
+    }
+}
+
+impl Nested {
+    /// Destructures `base` ( bound by the outer pattern ) by another `retrieve!` in the same mode.
+    fn build_statement(&self, base: TokenStream, context: &Context) -> TokenStream {
+        let Nested { tokens, ty, .. } = self;
+        let ty = ty.as_ref().unwrap(/* filled by the metadata before building */);
+        let mode = if context.cloning {
+            Some(quote!(clone:))
+        } else if context.assigning {
+            Some(quote!(assign:))
+        } else {
+            None
+        };
+        quote!(
+            ::kozo::retrieve!(#mode exhaustive #tokens from #base as #ty);
+        )
+    }
+}
+
+
+impl ToTokens for Target {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let expr = &self.expr;
+        tokens.extend(match expr {
+            Expr::Path(_) | Expr::Field(_) | Expr::Paren(_) |
+            Expr::Call(_) | Expr::MethodCall(_) | Expr::Index(_) => quote!(#expr),
+            _ => quote!((#expr)),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::{retrieve::Retrieve, Build};
+
+    #[test]
+    fn build_plain() {
+        let case = parse2::<Retrieve>(quote!(
+            a, b from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let a = s.a;
+                let b = s.b;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_nested_renamed_borrowed() {
+        let case = parse2::<Retrieve>(quote!(
+            a, x @ b.c from &self.sample
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let a = &self.sample.a;
+                let x = &self.sample.b.c;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_variant() {
+        let case = parse2::<Retrieve>(quote!(
+            name, id from f as F::Other else { return None }
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let F::Other { name, id, .. } = f else { return None };
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_variant_cloned() {
+        let case = parse2::<Retrieve>(quote!(
+            name clone, id copy from f as F::Other else { return None }
+        )).unwrap(/* this parsing passed in parser::test */);
+        let name = quote::format_ident!("__kozo_name", span = proc_macro2::Span::mixed_site());
+        let id = quote::format_ident!("__kozo_id", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let F::Other { name: ref #name, id: ref #id, .. } = f else { return None };
+                let name = (#name).clone();
+                let id = *#id;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_tuple_variant() {
+        let case = parse2::<Retrieve>(quote!(
+            y @ 1 from p as Pair::Two
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let Pair::Two(_, y, ..) = p;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_variant_nested() {
+        let case = parse2::<Retrieve>(quote!(
+            c, x @ inner.d from &s.b.f as F::Nested else { panic!() }
+        )).unwrap(/* this parsing passed in parser::test */);
+        let hidden = quote::format_ident!("__kozo_inner", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let F::Nested { c, inner: #hidden, .. } = &s.b.f else { panic!() };
+                let x = &#hidden.d;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_nullable() {
+        let case = parse2::<Retrieve>(quote!(
+            c @ b?.c, e @ b?.d?ok.e from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let c = s.b.map(|#v| #v.c);
+                let e = s.b.and_then(|#v| #v.d.ok()).map(|#v| #v.e);
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_nullable_borrowed_with_fallback() {
+        let case = parse2::<Retrieve>(quote!(
+            c @ b?.c, d from &mut s else { return }
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let ::core::option::Option::Some(c) = s.b.as_mut().map(|#v| &mut #v.c) else { return };
+                let d = &mut s.d;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_index() {
+        let case = parse2::<Retrieve>(quote!(
+            first @ d[0], head @ d[..3], v @ by_name["key"], e @ d[0]?.e from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let first = s.d[0];
+                let head = &s.d[..3];
+                let v = s.by_name["key"];
+                let e = s.d.get(0).map(|#v| &#v.e);
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_index_borrowed() {
+        let case = parse2::<Retrieve>(quote!(
+            first @ d[0]?, last @ d[i + 1] from &mut s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let first = s.d.get_mut(0);
+                let last = &mut s.d[i + 1];
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_method() {
+        let case = parse2::<Retrieve>(quote!(
+            len @ d.len(), c @ b.get(0)?.c, id @ b.find("x")?ok from &s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let len = s.d.len();
+                let c = s.b.get(0).map(|#v| &#v.c);
+                let id = s.b.find("x").ok();
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_shared_receiver() {
+        let case = parse2::<Retrieve>(quote!(
+            name @ user().name, d, id @ user().id from &mut load()
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = quote::format_ident!("__kozo_target", span = proc_macro2::Span::mixed_site());
+        let user = quote::format_ident!("__kozo_user", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let #target = &mut load();
+                let #user = #target.user();
+                let name = &mut #user.name;
+                let d = &mut #target.d;
+                let id = &mut #user.id;
+            ).to_string()
+        );
+
+        let case = parse2::<Retrieve>(quote!(
+            name @ b.user().name, id @ b.user().id, first @ first()?, tags @ b.user().c().tags, last @ first()?, c @ b.user().c() clone from &s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let user = quote::format_ident!("__kozo_user", span = proc_macro2::Span::mixed_site());
+        let c = quote::format_ident!("__kozo_c", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let #user = s.b.user();
+                let name = &#user.name;
+                let id = &#user.id;
+                let first = s.first();
+                let #c = #user.c();
+                let tags = &#c.tags;
+                let last = s.first();
+                let c = (#c).clone();
+            ).to_string()
+        );
+    }
+    #[test]
+    fn build_default() {
+        let case = parse2::<Retrieve>(quote!(
+            timeout = 30, c @ b?.c = C::default() from cfg
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let timeout = cfg.timeout.unwrap_or_else(|| 30);
+                let c = cfg.b.map(|#v| #v.c).unwrap_or_else(|| C::default());
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_default_borrowed() {
+        let case = parse2::<Retrieve>(quote!(
+            name: Cow<str> = "anon".into(), timeout = 30 copy from &cfg; retries = 3 from &mut cfg
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let name: Cow<str> = match cfg.name.as_ref() {
+                    ::core::option::Option::Some(#v) => ::std::borrow::Cow::Borrowed(#v),
+                    ::core::option::Option::None => ::std::borrow::Cow::Owned("anon".into()),
+                };
+                let timeout = cfg.timeout.as_ref().copied().unwrap_or_else(|| 30);
+                let retries = cfg.retries.get_or_insert_with(|| 3);
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_transforms() {
+        let case = parse2::<Retrieve>(quote!(
+            a as u64, name into String, tags clone, len @ d => d.len(), x: u32 from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let a = (s.a) as u64;
+                let name = ::core::convert::Into::<String>::into(s.name);
+                let tags = (&s.tags).clone();
+                let len = {
+                    let d = s.d;
+                    d.len()
+                };
+                let x: u32 = s.x;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_nullable_transforms() {
+        let case = parse2::<Retrieve>(quote!(
+            c @ b?.c as u64, len @ b?.d => d.len() from &s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let c = s.b.as_ref().map(|#v| &#v.c).map(|#v| (#v) as u64);
+                let len = s.b.as_ref().map(|#v| &#v.d).map(|d| d.len());
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_cloning() {
+        let case = parse2::<Retrieve>(quote!(
+            clone: a, c @ b?.c, id copy from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let a = (&s.a).clone();
+                let c = s.b.as_ref().map(|#v| &#v.c).cloned();
+                let id = *&s.id;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_assigning() {
+        let case = parse2::<Retrieve>(quote!(
+            assign: a, b.c => existing_c, total += count as u64, b?.d => self.last from s else { continue }
+        )).unwrap(/* this parsing passed in parser::test */);
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                a = s.a;
+                existing_c = s.b.c;
+                total += (s.count) as u64;
+                let ::core::option::Option::Some(#v) = s.b.map(|#v| #v.d) else { continue };
+                self.last = #v;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_groups() {
+        let case = parse2::<Retrieve>(quote!(
+            => clone: a, b from req; timeout from &cfg; user_id @ id from session
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let a = (&req.a).clone();
+                let b = (&req.b).clone();
+                let timeout = (&cfg.timeout).clone();
+                let user_id = (&session.id).clone();
+                (a, b, timeout, user_id)
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_wildcard() {
+        let case = parse2::<Retrieve>(quote!(
+            .. except b from &s as Sample
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                __kozo_Sample!{ retrieve { .. } { except b from &s as Sample } }
+            ).to_string()
+        );
+
+        let case = parse2::<Retrieve>(quote!(
+            .. [a: u8, b: B, c: Vec<u8>] except b from &s as Sample
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let Sample { a, c, .. } = &s;
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_exhaustive() {
+        let case = parse2::<Retrieve>(quote!(
+            exhaustive a, x @ b from &s as Sample; exhaustive p0 @ 0, p1 @ 1 from p as Pair
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let Sample { a, b: x, } = &s;
+                let Pair(p0, p1,) = p;
+            ).to_string()
+        );
+
+        let case = parse2::<Retrieve>(quote!(
+            exhaustive [a: u8, b: crate::B] a, b { c, d { e } } from &s as Sample
+        )).unwrap(/* this parsing passed in parser::test */);
+        let b = quote::format_ident!("__kozo_b", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let Sample { a, b: #b, } = &s;
+                ::kozo::retrieve!(exhaustive c, d { e } from #b as crate::B);
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_each() {
+        let case = parse2::<Retrieve>(quote!(
+            each a: HashSet<_>, b.c from &samples
+        )).unwrap(/* this parsing passed in parser::test */);
+        let item = quote::format_ident!("__kozo_item", span = proc_macro2::Span::mixed_site());
+        let (a, c) = (
+            quote::format_ident!("__kozo_a", span = proc_macro2::Span::mixed_site()),
+            quote::format_ident!("__kozo_c", span = proc_macro2::Span::mixed_site()),
+        );
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let mut a: HashSet<_> = ::core::default::Default::default();
+                let mut c: ::std::vec::Vec<_> = ::core::default::Default::default();
+                for #item in &samples {
+                    let (#a, #c,) = {
+                        let a = &(*#item).a;
+                        let c = &(*#item).b.c;
+                        (a, c,)
+                    };
+                    ::core::iter::Extend::extend(&mut a, ::core::iter::once(#a));
+                    ::core::iter::Extend::extend(&mut c, ::core::iter::once(#c));
+                }
+            ).to_string()
+        )
+    }
+    #[test]
+    fn build_expression() {
+        let case = parse2::<Retrieve>(quote!(
+            => a, b.d from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let a = s.a;
+                let d = s.b.d;
+                (a, d)
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_expression_single() {
+        let case = parse2::<Retrieve>(quote!(
+            => b.d from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let d = s.b.d;
+                d
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_into() {
+        let case = parse2::<Retrieve>(quote!(
+            a, x @ b.c from s; d into String from t into crate::Summary
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let a = s.a;
+                let x = s.b.c;
+                let d = ::core::convert::Into::<String>::into(t.d);
+                crate::Summary { a, x, d }
+            }).to_string()
+        )
+    }
+}
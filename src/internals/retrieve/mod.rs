@@ -1,46 +1,333 @@
+use std::iter;
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
-use syn::{token::Comma, parse::Parse};
-use super::Build;
+use quote::{quote, ToTokens};
+use super::metadata::{FieldMetadata, Deferred};
+use syn::{punctuated::Punctuated, token::{Comma, Semi, Colon, At, Dot, Dot2, Bracket, Brace, Paren, FatArrow, And, Mut, As, Else, Question}, Expr, Member, Block, Type, BinOp};
+
+mod parser;
+mod builder;
+mod attribute;
+
 
 mod keyword {
     syn::custom_keyword!(from);
+    syn::custom_keyword!(ok);
+    syn::custom_keyword!(into);
+    syn::custom_keyword!(clone);
+    syn::custom_keyword!(copy);
+    syn::custom_keyword!(assign);
+    syn::custom_keyword!(except);
+    syn::custom_keyword!(each);
+    syn::custom_keyword!(exhaustive);
 }
 
 pub(super) struct Retrieve {
-    idents: Vec<Ident>,
-    _from:  keyword::from,
-    target: Ident,
-}
-
-impl Parse for Retrieve {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Ok(Self {
-            idents: {
-                let mut idents = vec![
-                    input.parse::<Ident>()?
-                ];
-                while !input.peek(keyword::from) {
-                    input.parse::<Comma>()?;
-                    idents.push(input.parse::<Ident>()?)
+    expression: Option<FatArrow>,
+    mode:       Option<Mode>,
+    groups:     Punctuated<Group, Semi>,
+    /// `a, x @ b.c from s into Summary` builds `Summary { a, x }`
+    into:       Option<(keyword::into, syn::Path)>,
+    /// when a wildcard without fields, or nested entries without their types, needs the metadata
+    deferred:   Option<Deferred>,
+}
+impl Retrieve {
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.groups.iter().flat_map(|group| &group.entries)
+    }
+    /// Names bound by all entries, including the ones in nested `{ .. }`
+    pub fn bindings(&self) -> Vec<Ident> {
+        fn collect<'e>(entries: impl IntoIterator<Item = &'e Entry>, bindings: &mut Vec<Ident>) {
+            for entry in entries {
+                match &entry.nested {
+                    Some(nested) => collect(&nested.entries, bindings),
+                    None => bindings.push(entry.binding()),
                 }
-                idents
+            }
+        }
+        let mut bindings = Vec::new();
+        collect(self.entries(), &mut bindings);
+        bindings
+    }
+}
+
+/// `#[retrieving(a, b.c)] fn handle(req: Request) { .. }`
+/// 
+/// Without `from ..`, retrieves from the first parameter.
+pub(super) struct Retrieving {
+    retrieve: Retrieve,
+    function: syn::ItemFn,
+}
+
+/// `a, b.c from s`
+/// 
+/// `each a, b.c from samples` collects values from every item of `samples`.
+/// 
+/// `exhaustive a, b { c, d } from s as Sample` destructures without `..`.
+pub(super) struct Group {
+    each:       Option<keyword::each>,
+    exhaustive: Option<Exhaustive>,
+    entries:    Punctuated<Entry, Comma>,
+    wildcard:   Option<Wildcard>,
+    _from:      keyword::from,
+    target:     Target,
+    pattern:    Option<Pattern>,
+    fallback:   Option<(Else, Block)>,
+}
+
+/// `.. except a, b` : all fields of the type given by `as Type`, except `a` and `b`
+/// 
+/// Fields are filled in `[ ]` after `..` by the metadata macro `define!` emits.
+pub(super) struct Wildcard {
+    dots:   Dot2,
+    fields: Option<(Bracket, Punctuated<FieldMetadata, Comma>)>,
+    except: Option<(keyword::except, Punctuated<Ident, Comma>)>,
+    /// tokens from `..` to the end of input
+    rest:   TokenStream,
+}
+
+/// Types of fields are filled in `[ ]` after `exhaustive` by the metadata macro
+/// when some entries are nested like `b { c, d }`.
+pub(super) struct Exhaustive {
+    _exhaustive: keyword::exhaustive,
+    fields:      Option<(Bracket, Punctuated<FieldMetadata, Comma>)>,
+    /// tokens after `exhaustive` to the end of input
+    rest:        TokenStream,
+}
+
+pub(super) enum Mode {
+    /// `clone: a, b.c from s`
+    Clone {
+        _clone: keyword::clone,
+        _colon: Colon,
+    },
+    /// `assign: a, b.c => existing_c, total += count from s`
+    Assign {
+        _assign: keyword::assign,
+        _colon:  Colon,
+    },
+}
+
+pub(super) struct Entry {
+    rename:    Option<(Ident, At)>,
+    path:      Path,
+    ascribed:  Option<(Colon, Type)>,
+    /// `timeout = 30` : evaluated only when the value is `None`
+    default:   Option<(syn::token::Eq, Expr)>,
+    transform: Option<Transform>,
+    assignee:  Option<Assignee>,
+    nested:    Option<Nested>,
+}
+impl Entry {
+    pub fn binding(&self) -> Ident {
+        self.path.binding(self.rename.as_ref().map(|(name, _at)| name))
+    }
+}
+
+/// `b { c, d }` : exhaustively destructures `b` by another `retrieve!`
+pub(super) struct Nested {
+    _brace:  Brace,
+    entries: Punctuated<Entry, Comma>,
+    /// tokens in `{ }` given to the inner `retrieve!`
+    tokens:  TokenStream,
+    /// the type of `b`, known by the metadata of the outer type
+    ty:      Option<syn::Path>,
+}
+
+/// Where the value is assigned in `assign:` mode ( the binding name by default )
+pub(super) enum Assignee {
+    /// `b.c => existing_c`
+    Arrow {
+        _arrow: FatArrow,
+        place:  Expr,
+    },
+    /// `total += count`
+    Compound {
+        place: Expr,
+        op:    BinOp,
+    },
+}
+
+/// Applied to the retrieved value ( inside `.map` when it's wrapped in `Option` by nullable steps )
+pub(super) enum Transform {
+    /// `a as u64`
+    As {
+        _as: As,
+        ty:  Type,
+    },
+    /// `name into String`
+    Into {
+        _into: keyword::into,
+        ty:    Type,
+    },
+    /// `tags clone`
+    Clone {
+        _clone: keyword::clone,
+    },
+    /// `id copy`
+    Copy {
+        _copy: keyword::copy,
+    },
+    /// `len @ d => d.len()`
+    Map {
+        _arrow: FatArrow,
+        expr:   Expr,
+    },
+}
+
+/// `b?.c` : `b` is `Option<_>`
+/// 
+/// `b?ok.c` : `b` is `Result<_, _>`
+/// 
+/// `d[0]?` : by `d.get(0)`
+/// 
+/// `user()?.name` : `user()` returns `Option<_>`
+pub(super) struct Path {
+    /// `StepKind::Field`, or `StepKind::Method` when the group has no pattern
+    root: Step,
+    rest: Vec<Step>,
+} impl Path {
+    pub fn root_member(&self) -> &Member {
+        match &self.root.kind {
+            StepKind::Field { _dot, member } => member,
+            _ => unreachable!(/* rejected in parser */),
+        }
+    }
+    /// The last field or method name, by which the value is bound by default
+    pub fn last(&self) -> Member {
+        iter::once(&self.root).chain(&self.rest).rev()
+            .find_map(|step| match &step.kind {
+                StepKind::Field { _dot, member } => Some(member.clone()),
+                StepKind::Method { method, .. } => Some(Member::Named(method.clone())),
+                StepKind::Index { .. } => None,
+            })
+            .unwrap(/* root is not an index */)
+    }
+    /// Named fields from the root, or `None` when it has other kinds of steps
+    pub fn field_names(&self) -> Option<Vec<&Ident>> {
+        iter::once(&self.root).chain(&self.rest)
+            .map(|step| match step {
+                Step { kind: StepKind::Field { member: Member::Named(name), .. }, nullable: None } => Some(name),
+                _ => None,
+            })
+            .collect()
+    }
+    pub fn is_nullable(&self) -> bool {
+        iter::once(&self.root).chain(&self.rest).any(|step| step.nullable.is_some())
+    }
+    pub fn ends_with_method(&self) -> bool {
+        matches!(self.rest.last().unwrap_or(&self.root).kind, StepKind::Method { .. })
+    }
+    /// `rename` of `rename @ path`, or the last field or method name
+    pub fn binding(&self, rename: Option<&Ident>) -> Ident {
+        match rename {
+            Some(name) => name.clone(),
+            None => match self.last() {
+                Member::Named(ident) => ident,
+                Member::Unnamed(_) => unreachable!(/* rejected in parser */),
             },
-            _from:  input.parse()?,
-            target: input.parse()?,
-        })
+        }
     }
 }
+pub(super) struct Step {
+    kind:     StepKind,
+    nullable: Option<Nullable>,
+}
+pub(super) enum StepKind {
+    /// `.c` ( or `c` at the root )
+    Field {
+        _dot:   Option<Dot>,
+        member: Member,
+    },
+    /// `[0]`, `[..3]`, `["key"]`
+    Index {
+        _bracket: Bracket,
+        index:    Box<Expr>,
+    },
+    /// `.len()` ( or `user()` at the root )
+    Method {
+        _dot:   Option<Dot>,
+        method: Ident,
+        _paren: Paren,
+        args:   Punctuated<Expr, Comma>,
+    },
+}
+pub(super) enum Nullable {
+    Option {
+        _question: Question,
+    },
+    Result {
+        _question: Question,
+        _ok:       keyword::ok,
+    },
+}
 
-impl Build for Retrieve {
-    fn build(self) -> TokenStream {
-        let mut result = TokenStream::new();
-        let target = self.target;
-        for ident in self.idents {
-            result.extend(quote!(
-                let #ident = #target.#ident;
-            ))
+pub(super) struct Target {
+    borrow: Borrow,
+    expr:   Expr,
+}
+#[derive(Clone)]
+pub(super) enum Borrow {
+    Move,
+    Ref(And),
+    Mut(And, Mut),
+}
+
+pub(super) struct Pattern {
+    _as:  As,
+    path: syn::Path,
+}
+
+/// Whether evaluating `expr` more than once is free from side effects
+pub(super) fn is_place(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(_) => true,
+        Expr::Field(field) => is_place(&field.base),
+        Expr::Index(index) => is_place(&index.expr),
+        Expr::Paren(paren) => is_place(&paren.expr),
+        Expr::Unary(unary) => matches!(unary.op, syn::UnOp::Deref(_)) && is_place(&unary.expr),
+        _ => false,
+    }
+}
+
+/// A place target like `s` or `self.inner` is used directly ( it may be `&mut` itself ),
+/// and other expression like `&mut s` is evaluated once by the returned statement binding it to `__kozo_<name>`.
+pub(super) fn hoist_unless_place(target: &Expr, name: &str) -> (TokenStream, TokenStream) {
+    if is_place(target) {
+        return (TokenStream::new(), quote!(#target))
+    }
+    let hidden = hidden_ident(name);
+    (quote!(let #hidden = #target;), quote!(#hidden))
+}
+
+/// `x` for a single value, or `(x, y, ..)`
+pub(super) fn build_tuple(values: &[impl ToTokens]) -> TokenStream {
+    match values {
+        [single] => quote!(#single),
+        multiple => quote!((#(#multiple),*)),
+    }
+}
+
+/// Errors when a name is bound more than once
+pub(super) fn check_bindings(bindings: impl IntoIterator<Item = Ident>) -> syn::Result<()> {
+    let mut checked = Vec::<Ident>::new();
+    for binding in bindings {
+        if let Some(first) = checked.iter().find(|b| **b == binding) {
+            let mut error = syn::Error::new(binding.span(),
+                format!("`{binding}` is retrieved more than once")
+            );
+            error.combine(syn::Error::new(first.span(),
+                format!("`{binding}` is first retrieved here")
+            ));
+            return Err(error)
         }
-        result
+        checked.push(binding)
     }
-}
\ No newline at end of file
+    Ok(())
+}
+
+/// `__kozo_<name>` invisible from the user's code
+pub(super) fn hidden_ident(name: impl quote::IdentFragment) -> Ident {
+    let mut ident = quote::format_ident!("__kozo_{}", name);
+    ident.set_span(proc_macro2::Span::mixed_site());
+    ident
+}
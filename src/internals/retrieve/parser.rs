@@ -0,0 +1,871 @@
+use proc_macro2::{TokenStream, TokenTree, Ident};
+use syn::{parse::{Parse, ParseStream}, punctuated::Punctuated, token, parse2, bracketed, braced, parenthesized, Expr, Member, Type};
+use super::*;
+
+impl Parse for Retrieve {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let source = input.fork().parse::<TokenStream>()?;
+
+        let expression = input.parse::<Option<token::FatArrow>>()?;
+        let mode = if (input.peek(keyword::clone) || input.peek(keyword::assign))
+                   && input.peek2(token::Colon) {
+            Some(input.parse::<Mode>()?)
+        } else {
+            None
+        };
+
+        let assigning = matches!(mode, Some(Mode::Assign { .. }));
+        if let (Some(arrow), true) = (&expression, assigning) {
+            return Err(syn::Error::new_spanned(arrow,
+                "`assign:` mode can't be used as an expression"
+            ))
+        }
+
+        let groups = {
+            let mut groups = Punctuated::<Group, token::Semi>::new();
+            groups.push_value(Group::parse(input, assigning)?);
+            while !input.is_empty() && !input.peek(keyword::into) {
+                groups.push_punct(input.parse()?);
+                if input.is_empty() || input.peek(keyword::into) {break}
+                groups.push_value(Group::parse(input, assigning)?);
+            }
+            groups
+        };
+        let into = if input.peek(keyword::into) {
+            let into = input.parse::<keyword::into>()?;
+            if assigning {
+                return Err(syn::Error::new(into.span, "`into ..` is not available in `assign:` mode"))
+            }
+            if let Some(arrow) = &expression {
+                return Err(syn::Error::new_spanned(arrow, "`into ..` makes `retrieve!` an expression without `=>`"))
+            }
+            Some((into, input.parse()?))
+        } else {
+            None
+        };
+        let deferred = groups.iter()
+            .find_map(|group| {
+                let pattern = group.pattern.as_ref()?;
+                let rest_len = match (&group.wildcard, &group.exhaustive) {
+                    (Some(wildcard), _) if wildcard.fields.is_none() => wildcard.rest.clone().into_iter().count() - 2/* `..` */,
+                    (_, Some(exhaustive)) if exhaustive.fields.is_none()
+                        && group.entries.iter().any(|entry| entry.nested.is_some()) => exhaustive.rest.clone().into_iter().count(),
+                    _ => return None,
+                };
+                Some((pattern, rest_len))
+            })
+            .map(|(pattern, rest_len)| {
+                let ty = pattern.path.segments.last().unwrap(/* `syn::Path` has at least 1 segment */).ident.clone();
+                let source = source.into_iter().collect::<Vec<_>>();
+                let end_of_marker = source.len() - rest_len;
+                Deferred {
+                    callback: "retrieve",
+                    ty,
+                    before: source[..end_of_marker].iter().cloned().collect(),
+                    after:  source[end_of_marker..].iter().cloned().collect(),
+                }
+            });
+        if !matches!(mode, Some(Mode::Clone { .. })) {
+            for group in groups.iter().filter(|group| group.pattern.is_none()) {
+                check_shared_calls(&group.entries)?
+            }
+        }
+        let retrieve = Self { expression, mode, groups, into, deferred };
+
+        if !assigning {
+            check_bindings(retrieve.bindings())?
+        }
+        Ok(retrieve)
+    }
+}
+
+impl Group {
+    fn parse(input: ParseStream, assigning: bool) -> syn::Result<Self> {
+        let each = if peek_group_keyword(input, keyword::each) {
+            let each = input.parse::<keyword::each>()?;
+            if assigning {
+                return Err(syn::Error::new(each.span, "`each` is not available in `assign:` mode"))
+            }
+            Some(each)
+        } else {
+            None
+        };
+        let exhaustive = if peek_group_keyword(input, keyword::exhaustive)
+        || (input.peek(keyword::exhaustive) && input.peek2(token::Bracket)) {
+            Some(Exhaustive {
+                _exhaustive: input.parse()?,
+                rest:        input.fork().parse()?,
+                fields:      FieldMetadata::parse_list(input)?,
+            })
+        } else {
+            None
+        };
+
+        let (mut entries, mut wildcard) = (Punctuated::new(), None);
+        loop {
+            if input.peek(token::Dot2) {
+                wildcard = Some(input.parse::<Wildcard>()?);
+                break
+            }
+            entries.push_value(Entry::parse(input, assigning)?);
+            if input.peek(keyword::from) {
+                break
+            }
+            entries.push_punct(input.parse()?);
+        }
+
+        let mut group = Self {
+            each,
+            exhaustive,
+            entries,
+            wildcard,
+            _from:   input.parse()?,
+            target:  input.parse()?,
+            pattern: if input.peek(token::As) {
+                Some(input.parse()?)
+            } else {
+                None
+            },
+            fallback: if input.peek(token::Else) {
+                Some((input.parse()?, input.parse()?))
+            } else {
+                None
+            },
+        };
+
+        if let Some((_else, _)) = &group.fallback {
+            let is_unwrapped = |entry: &Entry| entry.path.is_nullable() && entry.default.is_none();
+            if group.pattern.is_none() && !group.entries.iter().any(is_unwrapped) {
+                return Err(syn::Error::new_spanned(_else,
+                    "`else` is available only with `as Pattern` or an entry having `?` without default"
+                ))
+            }
+        }
+
+        if let Borrow::Mut(..) = group.target.borrow {
+            let defaulted_through_nullable = group.entries.iter()
+                .filter(|entry| entry.path.is_nullable() && !entry.path.ends_with_method())
+                .find_map(|entry| entry.default.as_ref());
+            if let Some((eq, _)) = defaulted_through_nullable {
+                return Err(syn::Error::new_spanned(eq,
+                    "default value through `?` is not available with `&mut` target"
+                ))
+            }
+        }
+
+        if let Some(wildcard) = &group.wildcard {
+            let Some(pattern) = &group.pattern else {
+                return Err(syn::Error::new_spanned(wildcard.dots,
+                    "`..` needs the type of target like `.. from s as Sample`"
+                ))
+            };
+            if let Some((_bracket, fields)) = &wildcard.fields {
+                let excepted = wildcard.except.iter().flat_map(|(_except, names)| names).collect::<Vec<_>>();
+                if let Some(unknown) = excepted.iter().find(|name| !fields.iter().any(|field| &field.name == **name)) {
+                    let ty = &pattern.path;
+                    return Err(syn::Error::new(unknown.span(), format!(
+                        "no field `{unknown}` in `{}`", quote::quote!(#ty).to_string().replace(' ', "")
+                    )))
+                }
+
+                for field in fields.iter().map(|field| &field.name).filter(|name| !excepted.contains(name)) {
+                    group.entries.push(Entry {
+                        rename:    None,
+                        path:      Path {
+                            root: Step {
+                                kind: StepKind::Field {
+                                    _dot:   None,
+                                    member: Member::Named(Ident::new(&field.to_string(), wildcard.dots.spans[0])),
+                                },
+                                nullable: None,
+                            },
+                            rest: Vec::new(),
+                        },
+                        ascribed:  None,
+                        default:   None,
+                        transform: None,
+                        assignee:  None,
+                        nested:    None,
+                    })
+                }
+            }
+        }
+
+        if let Some(nested) = group.entries.iter().find_map(|entry| entry.nested.as_ref()) {
+            if group.exhaustive.is_none() || group.each.is_some() {
+                return Err(syn::Error::new(nested._brace.span,
+                    "nested `{ .. }` is available only in `exhaustive` group without `each`"
+                ))
+            }
+        }
+        if let Some(exhaustive) = &group.exhaustive {
+            let Some(pattern) = &group.pattern else {
+                return Err(syn::Error::new(exhaustive._exhaustive.span,
+                    "`exhaustive` needs the type of target like `exhaustive a, b from s as Sample`"
+                ))
+            };
+            if let Some((_bracket, fields)) = &exhaustive.fields {
+                for entry in &mut group.entries {
+                    let Some(nested) = &mut entry.nested else {continue};
+                    let Member::Named(name) = entry.path.root_member() else {unreachable!(/* rejected in Entry::parse */)};
+                    let Some(field) = fields.iter().find(|field| field.name == *name) else {
+                        let ty = &pattern.path;
+                        return Err(syn::Error::new(name.span(), format!(
+                            "no field `{name}` in `{}`", quote::quote!(#ty).to_string().replace(' ', "")
+                        )))
+                    };
+                    let path = match &field.ty {
+                        Type::Path(syn::TypePath { qself: None, path })
+                        if path.segments.iter().all(|segment| segment.arguments.is_empty()) => path,
+                        _ => return Err(syn::Error::new(name.span(), format!(
+                            "`{name}` is not a struct to destructure by `{{ .. }}`"
+                        ))),
+                    };
+                    nested.ty = Some(path.clone())
+                }
+            }
+        }
+
+        if group.pattern.is_some() {
+            let method_root = group.entries.iter().find_map(|entry| match &entry.path.root.kind {
+                StepKind::Method { method, .. } => Some(method),
+                _ => None,
+            });
+            if let Some(method) = method_root {
+                return Err(syn::Error::new(method.span(),
+                    "method call can't be the root of a path with `as ..`, call it on a field like `b.len()`"
+                ))
+            }
+            let is_unnamed = |entry: &Entry| matches!(entry.path.root_member(), Member::Unnamed(_));
+            let first_is_unnamed = group.entries.first().map(is_unnamed).unwrap_or(false);
+            if let Some(entry) = group.entries.iter().find(|e| is_unnamed(e) != first_is_unnamed) {
+                return Err(syn::Error::new_spanned(entry.path.root_member(),
+                    "named fields and tuple fields can't be mixed in one pattern"
+                ))
+            }
+        }
+        Ok(group)
+    }
+}
+
+/// Errors when an entry takes what a method call shared with other entries returns by value,
+/// which they borrow or move from ( the call is evaluated only once )
+fn check_shared_calls(entries: &Punctuated<Entry, token::Comma>) -> syn::Result<()> {
+    for entry in entries {
+        let is_borrowed = matches!(entry.transform, Some(Transform::Clone { .. } | Transform::Copy { .. }));
+        let Some((len, key)) = entry.path.calls().pop() else {continue};
+        if is_borrowed || len != 1 + entry.path.rest.len() {
+            continue
+        }
+        let other = entries.iter()
+            .filter(|e| !std::ptr::eq(*e, entry))
+            .find(|e| e.path.calls().iter().any(|(_, k)| *k == key));
+        if let Some(other) = other {
+            let StepKind::Method { method, .. } = &entry.path.rest.last().unwrap_or(&entry.path.root).kind else {
+                unreachable!(/* calls end with a method */)
+            };
+            let mut error = syn::Error::new(method.span(), format!(
+                "`{method}()` is evaluated once for the entries sharing it, and can't be moved by one of them; \
+                retrieve it with `clone` / `copy`, or by another `retrieve!`"
+            ));
+            error.combine(syn::Error::new(other.binding().span(), format!("`{method}()` is also used here")));
+            return Err(error)
+        }
+    }
+    Ok(())
+}
+
+/// Whether `keyword` is not a field name but the keyword of group, followed by entries
+fn peek_group_keyword(input: ParseStream, keyword: impl syn::parse::Peek) -> bool {
+    input.peek(keyword) && (
+        (input.peek2(syn::Ident) && !input.peek2(keyword::from)
+          && !input.peek2(keyword::into) && !input.peek2(keyword::clone) && !input.peek2(keyword::copy))
+        || input.peek2(token::Dot2)
+    )
+}
+
+impl Parse for Wildcard {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            rest:   input.fork().parse()?,
+            dots:   input.parse()?,
+            fields: FieldMetadata::parse_list(input)?,
+            except: if input.peek(keyword::except) {
+                let except = input.parse()?;
+                let mut names = Punctuated::new();
+                names.push_value(input.parse()?);
+                while !input.peek(keyword::from) {
+                    names.push_punct(input.parse()?);
+                    names.push_value(input.parse()?);
+                }
+                Some((except, names))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl Parse for Mode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(keyword::assign) {
+            Ok(Self::Assign {
+                _assign: input.parse()?,
+                _colon:  input.parse()?,
+            })
+        } else {
+            Ok(Self::Clone {
+                _clone: input.parse()?,
+                _colon: input.parse()?,
+            })
+        }
+    }
+}
+
+impl Entry {
+    /// In `assign:` mode, `=> ..` after the path is not a transform but the assignee.
+    pub fn parse(input: ParseStream, assigning: bool) -> syn::Result<Self> {
+        let compound = if assigning {
+            parse_compound_assignee(input)?
+        } else {
+            None
+        };
+        let rename = if input.peek2(token::At) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let path = input.parse::<Path>()?;
+
+        if input.peek(token::Brace) {
+            let nested_buf;
+            let _brace = braced!(nested_buf in input);
+            let tokens = nested_buf.fork().parse::<TokenStream>()?;
+            let mut entries = Punctuated::new();
+            while !nested_buf.is_empty() {
+                entries.push_value(Entry::parse(&nested_buf, assigning)?);
+                if nested_buf.is_empty() {break}
+                entries.push_punct(nested_buf.parse()?);
+            }
+
+            if rename.is_some() || !path.rest.is_empty() || path.root.nullable.is_some()
+            || !matches!(path.root.kind, StepKind::Field { member: Member::Named(_), .. }) {
+                return Err(syn::Error::new(_brace.span,
+                    "nested `{ .. }` is available only for a field like `b { c, d }`"
+                ))
+            }
+            return Ok(Self {
+                rename, path, ascribed: None, default: None, transform: None, assignee: compound,
+                nested: Some(Nested { _brace, entries, tokens, ty: None }),
+            })
+        }
+
+        if let (None, Member::Unnamed(index)) = (&rename, path.last()) {
+            return Err(syn::Error::new(index.span,
+                "tuple field needs a binding name like `name @ 0`"
+            ))
+        }
+        let ascribed = if input.peek(token::Colon) {
+            Some((input.parse::<token::Colon>()?, input.parse()?))
+        } else {
+            None
+        };
+        if let (Some((colon, _)), true) = (&ascribed, assigning) {
+            return Err(syn::Error::new_spanned(colon,
+                "type ascription is not available in `assign:` mode"
+            ))
+        }
+        let default = if input.peek(token::Eq) && !input.peek(token::FatArrow) && !input.peek(token::EqEq) {
+            Some((input.parse()?, parse_expr_until(input, |rest|
+                rest.peek(token::Comma) || rest.peek(keyword::from) || rest.peek(token::As) || rest.peek(token::FatArrow)
+                || ((rest.peek(keyword::into) || rest.peek(keyword::clone) || rest.peek(keyword::copy))
+                    && !rest.peek2(token::Paren) /* not a method call like `.into()` */)
+            )?))
+        } else {
+            None
+        };
+
+        let transform = if input.peek(token::As)
+                        || input.peek(keyword::into)
+                        || input.peek(keyword::clone)
+                        || input.peek(keyword::copy)
+                        || (input.peek(token::FatArrow) && !assigning) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let assignee = match compound {
+            Some(compound) => Some(compound),
+            None => if assigning && input.peek(token::FatArrow) {
+                Some(Assignee::Arrow {
+                    _arrow: input.parse()?,
+                    place:  input.parse()?,
+                })
+            } else {
+                None
+            },
+        };
+
+        Ok(Self { rename, path, ascribed, default, transform, assignee, nested: None })
+    }
+}
+
+/// Parses `place +=` part of `place += path`, or returns `None` if the entry is not in this form.
+fn parse_compound_assignee(input: ParseStream) -> syn::Result<Option<Assignee>> {
+    let is_compound_op = |rest: ParseStream| {
+        rest.peek(token::AddEq) || rest.peek(token::SubEq) ||
+        rest.peek(token::MulEq) || rest.peek(token::DivEq) || rest.peek(token::RemEq) ||
+        rest.peek(token::AndEq) || rest.peek(token::OrEq) || rest.peek(token::CaretEq) ||
+        rest.peek(token::ShlEq) || rest.peek(token::ShrEq)
+    };
+
+    let fork = input.fork();
+    while !fork.is_empty() && !is_compound_op(&fork)
+    && !fork.peek(token::Comma) && !fork.peek(keyword::from) {
+        fork.parse::<TokenTree>()?;
+    }
+    if !is_compound_op(&fork) {
+        return Ok(None)
+    }
+
+    Ok(Some(Assignee::Compound {
+        place: parse_expr_until(input, is_compound_op)?,
+        op:    input.parse()?,
+    }))
+}
+
+impl Parse for Transform {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(token::As) {
+            Ok(Self::As {
+                _as: input.parse()?,
+                ty:  input.parse()?,
+            })
+        } else if input.peek(keyword::into) {
+            Ok(Self::Into {
+                _into: input.parse()?,
+                ty:    input.parse()?,
+            })
+        } else if input.peek(keyword::clone) {
+            Ok(Self::Clone {
+                _clone: input.parse()?,
+            })
+        } else if input.peek(keyword::copy) {
+            Ok(Self::Copy {
+                _copy: input.parse()?,
+            })
+        } else if input.peek(token::FatArrow) {
+            Ok(Self::Map {
+                _arrow: input.parse()?,
+                expr:   input.parse()?,
+            })
+        } else {
+            Err(input.error("expected one of `as`, `into`, `clone`, `copy` or `=>`"))
+        }
+    }
+}
+
+impl Path {
+    /// Parses a path together with it as written like `b.c[0]`, shown in messages
+    pub fn parse_with_label(input: ParseStream) -> syn::Result<(Self, String)> {
+        let fork = input.fork();
+        let path = fork.parse::<Path>()?;
+
+        let mut label = String::new();
+        while input.cursor() != fork.cursor() {
+            label.push_str(&input.parse::<TokenTree>()?.to_string())
+        }
+        Ok((path, label))
+    }
+}
+
+impl Parse for Path {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            root: Step {
+                kind:     parse_member_or_method(input, None)?,
+                nullable: parse_nullable(input)?,
+            },
+            rest: {
+                let mut rest = Vec::new();
+                while input.peek(token::Dot) || input.peek(token::Bracket) {
+                    rest.push(input.parse()?)
+                }
+                rest
+            },
+        })
+    }
+}
+impl Parse for Step {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind = if input.peek(token::Bracket) {
+            let index_buf;
+            StepKind::Index {
+                _bracket: bracketed!(index_buf in input),
+                index:    index_buf.parse()?,
+            }
+        } else {
+            let dot = input.parse()?;
+            parse_member_or_method(input, Some(dot))?
+        };
+        let nullable = parse_nullable(input)?;
+
+        if let (StepKind::Index { .. }, Some(Nullable::Result { _question, .. })) = (&kind, &nullable) {
+            return Err(syn::Error::new(_question.span, "`?ok` is not available for index, use `?`"))
+        }
+        Ok(Self { kind, nullable })
+    }
+}
+fn parse_member_or_method(input: ParseStream, dot: Option<token::Dot>) -> syn::Result<StepKind> {
+    let member = input.parse::<Member>()?;
+    match member {
+        Member::Named(method) if input.peek(token::Paren) => {
+            let args_buf;
+            Ok(StepKind::Method {
+                _dot:   dot,
+                method,
+                _paren: parenthesized!(args_buf in input),
+                args:   args_buf.parse_terminated(Expr::parse)?,
+            })
+        },
+        member => Ok(StepKind::Field { _dot: dot, member }),
+    }
+}
+fn parse_nullable(input: ParseStream) -> syn::Result<Option<Nullable>> {
+    if input.peek(token::Question) {
+        Ok(Some(input.parse()?))
+    } else {
+        Ok(None)
+    }
+}
+impl Parse for Nullable {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _question = input.parse()?;
+        if input.peek(keyword::ok) {
+            Ok(Self::Result { _question, _ok: input.parse()? })
+        } else {
+            Ok(Self::Option { _question })
+        }
+    }
+}
+
+impl Parse for Target {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            borrow: input.parse()?,
+            expr:   parse_expr_until(input, |rest|
+                rest.peek(token::As) || rest.peek(token::Else) || rest.peek(token::Semi) || rest.peek(keyword::into)
+            )?,
+        })
+    }
+}
+impl Parse for Borrow {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(token::And) {
+            Ok(Self::Move)
+        } else if input.peek2(token::Mut) {
+            Ok(Self::Mut(input.parse()?, input.parse()?))
+        } else {
+            Ok(Self::Ref(input.parse()?))
+        }
+    }
+}
+
+impl Parse for Pattern {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            _as:  input.parse()?,
+            path: input.parse()?,
+        })
+    }
+}
+
+/// `syn::Expr` can't be parsed directly when `as ...` or `else { .. }` follows
+/// because they're taken as a part of the expression.
+/// ( and `;` or `into ..` to end the group )
+fn parse_expr_until(input: ParseStream, stop: fn(ParseStream) -> bool) -> syn::Result<Expr> {
+    let mut tokens = TokenStream::new();
+    let mut after_path_separator = false /* then a keyword is a part of path like `String::from` or `.into()` */;
+    let mut generics_depth = 0 /* in `::<K, V>` or `<T as Trait>::` where `,` and `as` don't end the expression */;
+    let mut after_dash = false /* then `>` is a part of `->` */;
+    while !input.is_empty() && (after_path_separator || generics_depth > 0 || !stop(input)) {
+        let token = input.parse::<TokenTree>()?;
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' if tokens.is_empty() || after_path_separator || generics_depth > 0 => generics_depth += 1,
+                '>' if generics_depth > 0 && !after_dash => generics_depth -= 1,
+                _ => (),
+            }
+        }
+        after_path_separator = matches!(&token, TokenTree::Punct(punct) if matches!(punct.as_char(), ':' | '.'));
+        after_dash = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '-');
+        tokens.extend([token])
+    }
+    if tokens.is_empty() {
+        return Err(input.error("expected expression"))
+    }
+    parse2(tokens)
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::retrieve::{Retrieve, Borrow, Assignee, Transform};
+
+    #[test]
+    fn parse_bindings() {
+        let case = parse2::<Retrieve>(quote!(
+            a, x @ b.c, b.d from s
+        )).unwrap();
+        assert_eq!(
+            case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(),
+            ["a", "x", "d"]
+        )
+    }
+    #[test]
+    fn parse_borrow() {
+        let case = parse2::<Retrieve>(quote!(
+            a from &mut s
+        )).unwrap();
+        assert!(matches!(case.groups[0].target.borrow, Borrow::Mut(_, _)))
+    }
+    #[test]
+    fn parse_unnamed_tuple_field() {
+        assert!(parse2::<Retrieve>(quote!(
+            0 from pair as Pair
+        )).is_err())
+    }
+    #[test]
+    fn parse_mixed_pattern_fields() {
+        assert!(parse2::<Retrieve>(quote!(
+            name, x @ 0 from f as F::Other else { return }
+        )).is_err())
+    }
+    #[test]
+    fn parse_fallback() {
+        assert!(parse2::<Retrieve>(quote!(
+            name from f as F::Other else { return }
+        )).is_ok());
+        assert!(parse2::<Retrieve>(quote!(
+            c @ b?.c from s else { return }
+        )).is_ok());
+        assert!(parse2::<Retrieve>(quote!(
+            a from s else { return 0 }
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            c @ b?.c = 0 from s else { return }
+        )).is_err());
+    }
+    #[test]
+    fn parse_assignees() {
+        let case = parse2::<Retrieve>(quote!(
+            assign: a, b.c => existing_c, self.total += count from s
+        )).unwrap();
+        assert_eq!(
+            case.entries().map(|e| match &e.assignee {
+                None => String::from("-"),
+                Some(Assignee::Arrow { place, .. }) => quote!(#place).to_string(),
+                Some(Assignee::Compound { place, op }) => quote!(#place #op).to_string(),
+            }).collect::<Vec<_>>(),
+            ["-", "existing_c", "self . total +="]
+        )
+    }
+    #[test]
+    fn parse_assigning_expression() {
+        assert!(parse2::<Retrieve>(quote!(
+            => assign: a from s
+        )).is_err())
+    }
+    #[test]
+    fn parse_groups() {
+        let case = parse2::<Retrieve>(quote!(
+            a, b from req; timeout from &cfg; user_id @ id from session;
+        )).unwrap();
+        assert_eq!(case.groups.len(), 3);
+        assert_eq!(
+            case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(),
+            ["a", "b", "timeout", "user_id"]
+        )
+    }
+    #[test]
+    fn parse_clash() {
+        assert!(parse2::<Retrieve>(quote!(
+            a, b from req; a from cfg
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            a, x @ b.a from req
+        )).is_ok());
+        assert!(parse2::<Retrieve>(quote!(
+            a, b.a from req
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            assign: total += a from req; total += a from cfg
+        )).is_ok())
+    }
+    #[test]
+    fn parse_shared_calls() {
+        assert!(parse2::<Retrieve>(quote!(
+            n @ count(), m @ count() from s
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            user @ b.user(), name @ b.user().name from s
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            name @ b.user().name, user @ b.user() from s
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            user @ b.user() clone, name @ b.user().name from s
+        )).is_ok());
+        assert!(parse2::<Retrieve>(quote!(
+            clone: n @ count(), m @ count() from s
+        )).is_ok());
+        assert!(parse2::<Retrieve>(quote!(
+            a @ first()?, b @ first()? from s
+        )).is_ok())
+    }
+    #[test]
+    fn parse_wildcard() {
+        let case = parse2::<Retrieve>(quote!(
+            x @ b.c, .. except a, b from s as Sample
+        )).unwrap();
+        let deferred = case.deferred.unwrap();
+        assert_eq!(deferred.ty.to_string(), "Sample");
+        assert_eq!(deferred.before.to_string(), quote!(x @ b.c, ..).to_string());
+        assert_eq!(deferred.after.to_string(), quote!(except a, b from s as Sample).to_string());
+
+        let case = parse2::<Retrieve>(quote!(
+            x @ b.c, .. [a: u8, b: B, c: Vec<u8>] except a, b from s as Sample
+        )).unwrap();
+        assert!(case.deferred.is_none());
+        assert_eq!(
+            case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(),
+            ["x", "c"]
+        )
+    }
+    #[test]
+    fn parse_wildcard_unknown_except() {
+        assert!(parse2::<Retrieve>(quote!(
+            .. [a: u8, b: B] except x from s as Sample
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            .. from s
+        )).is_err())
+    }
+    #[test]
+    fn parse_each() {
+        let case = parse2::<Retrieve>(quote!(
+            each a, b from samples; each from s
+        )).unwrap();
+        assert!(case.groups[0].each.is_some());
+        assert!(case.groups[1].each.is_none());
+        assert_eq!(
+            case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(),
+            ["a", "b", "each"]
+        )
+    }
+    #[test]
+    fn parse_index() {
+        let case = parse2::<Retrieve>(quote!(
+            first @ d[0], head @ d[..3], v @ by_name["key"], e @ d[0]?.e from s
+        )).unwrap();
+        assert_eq!(
+            case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(),
+            ["first", "head", "v", "e"]
+        );
+        assert!(parse2::<Retrieve>(quote!(
+            d[0]?ok from s
+        )).is_err())
+    }
+    #[test]
+    fn parse_method() {
+        let case = parse2::<Retrieve>(quote!(
+            len @ d.len(), user().name, d.first()?, get(0)?ok from s
+        )).unwrap();
+        assert_eq!(
+            case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(),
+            ["len", "name", "first", "get"]
+        );
+        assert!(parse2::<Retrieve>(quote!(
+            user().name from s as Sample
+        )).is_err())
+    }
+    #[test]
+    fn parse_into() {
+        let case = parse2::<Retrieve>(quote!(
+            a, x @ b.c from s; d from &t into Summary
+        )).unwrap();
+        assert_eq!(case.groups.len(), 2);
+        assert!(case.into.is_some());
+        assert!(parse2::<Retrieve>(quote!(
+            => a from s into Summary
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            assign: a from s into Summary
+        )).is_err())
+    }
+    #[test]
+    fn parse_default() {
+        let case = parse2::<Retrieve>(quote!(
+            timeout = 30 as u64, name = "anon".into() clone, c @ b?.c = C::default() from cfg
+        )).unwrap();
+        assert!(case.entries().all(|e| e.default.is_some()));
+        assert!(matches!(case.groups[0].entries[0].transform, Some(Transform::As { .. })));
+        assert!(matches!(case.groups[0].entries[1].transform, Some(Transform::Clone { .. })));
+
+        let case = parse2::<Retrieve>(quote!(
+            name = String::from("anon"), tags = Vec::<String>::new().clone() from cfg
+        )).unwrap();
+        assert!(case.entries().all(|e| e.default.is_some()));
+        assert!(case.groups[0].entries[1].transform.is_none());
+
+        let case = parse2::<Retrieve>(quote!(
+            index = HashMap::<String, Vec<u8>>::new(), f = <Vec<u8> as Default>::default() as Vec<u8>, limit = a < b, c from cfg
+        )).unwrap();
+        assert_eq!(case.entries().map(|e| e.binding().to_string()).collect::<Vec<_>>(), ["index", "f", "limit", "c"]);
+        assert!(case.groups[0].entries[0].transform.is_none());
+        assert!(matches!(case.groups[0].entries[1].transform, Some(Transform::As { .. })));
+        assert!(parse2::<Retrieve>(quote!(
+            timeout = 30, c @ b?.c = C::default() from &mut cfg
+        )).is_err())
+    }
+    #[test]
+    fn parse_exhaustive() {
+        let case = parse2::<Retrieve>(quote!(
+            exhaustive a, b from s as Sample
+        )).unwrap();
+        assert!(case.groups[0].exhaustive.is_some());
+        assert!(case.deferred.is_none());
+
+        let case = parse2::<Retrieve>(quote!(
+            exhaustive a, b { c, d { e } } from s as Sample
+        )).unwrap();
+        let deferred = case.deferred.unwrap();
+        assert_eq!(deferred.before.to_string(), quote!(exhaustive).to_string());
+        assert_eq!(deferred.after.to_string(), quote!(a, b { c, d { e } } from s as Sample).to_string());
+
+        let case = parse2::<Retrieve>(quote!(
+            exhaustive [a: u8, b: crate::B] a, b { c, d { e } } from s as Sample
+        )).unwrap();
+        assert!(case.deferred.is_none());
+        assert_eq!(case.bindings().iter().map(|b| b.to_string()).collect::<Vec<_>>(), ["a", "c", "e"]);
+
+        assert!(parse2::<Retrieve>(quote!(
+            exhaustive a, b from s
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            a, b { c } from s as Sample
+        )).is_err());
+        assert!(parse2::<Retrieve>(quote!(
+            exhaustive [a: u8, b: Vec<B>] a, b { c } from s as Sample
+        )).is_err());
+    }
+    #[test]
+    fn parse_missing_from() {
+        assert!(parse2::<Retrieve>(quote!(
+            a, b s
+        )).is_err())
+    }
+}
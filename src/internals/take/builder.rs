@@ -0,0 +1,106 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::internals::Build;
+use crate::internals::retrieve::{hoist_unless_place, build_tuple};
+use super::{Take, Replace, Replacement};
+
+impl Build for Take {
+    fn build(self) -> TokenStream {
+        let (mut statements, base) = hoist_unless_place(&self.target, "target");
+
+        let bindings = self.entries.iter().map(|entry| entry.binding()).collect::<Vec<_>>();
+        for (entry, binding) in self.entries.iter().zip(&bindings) {
+            let value = entry.path.build_mut_place(base.clone(), |place| quote!(
+                ::core::mem::take(&mut #place)
+            ));
+            statements.extend(quote!(let #binding = #value;))
+        }
+
+        match self.expression {
+            None => statements,
+            Some(_) => {
+                let value = build_tuple(&bindings);
+                quote!({
+                    #statements
+                    #value
+                })
+            },
+        }
+    }
+}
+
+impl Build for Replace {
+    fn build(self) -> TokenStream {
+        let (statements, base) = hoist_unless_place(&self.target, "target");
+
+        let values = self.replacements.iter().map(|Replacement { path, value, .. }|
+            path.build_mut_place(base.clone(), |place| quote!(
+                ::core::mem::replace(&mut #place, #value)
+            ))
+        ).collect::<Vec<_>>();
+        let value = build_tuple(&values);
+        quote!({
+            #statements
+            #value
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::{take::{Take, Replace}, Build};
+
+    #[test]
+    fn build_take() {
+        let case = parse2::<Take>(quote!(
+            a, x @ b.c, d[0] from self.state
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!(
+                let a = ::core::mem::take(&mut self.state.a);
+                let x = ::core::mem::take(&mut self.state.b.c);
+                let d = ::core::mem::take(&mut self.state.d[0]);
+            ).to_string()
+        );
+
+        let case = parse2::<Take>(quote!(
+            => a, b.c from &mut s
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = quote::format_ident!("__kozo_target", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &mut s;
+                let a = ::core::mem::take(&mut #target.a);
+                let c = ::core::mem::take(&mut #target.b.c);
+                (a, c)
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_replace() {
+        let case = parse2::<Replace>(quote!(
+            b.c = String::new() from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                ::core::mem::replace(&mut s.b.c, String::new())
+            }).to_string()
+        );
+
+        let case = parse2::<Replace>(quote!(
+            a = 0, b.c = String::new() from s
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                (::core::mem::replace(&mut s.a, 0), ::core::mem::replace(&mut s.b.c, String::new()))
+            }).to_string()
+        )
+    }
+}
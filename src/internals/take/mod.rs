@@ -0,0 +1,42 @@
+use proc_macro2::Ident;
+use syn::{punctuated::Punctuated, token::{Comma, At, FatArrow}, Expr};
+use super::retrieve::Path;
+
+mod parser;
+mod builder;
+
+
+mod keyword {
+    syn::custom_keyword!(from);
+}
+
+/// `a, x @ b.c from s` : moves the values out of `s` by `mem::take`, leaving `Default` values
+pub(super) struct Take {
+    expression: Option<FatArrow>,
+    entries:    Punctuated<Entry, Comma>,
+    _from:      keyword::from,
+    target:     Expr,
+}
+
+pub(super) struct Entry {
+    rename: Option<(Ident, At)>,
+    path:   Path,
+}
+impl Entry {
+    pub fn binding(&self) -> Ident {
+        self.path.binding(self.rename.as_ref().map(|(name, _at)| name))
+    }
+}
+
+/// `b.c = new, a = 0 from s` : puts the new values by `mem::replace`, yielding the old ones
+pub(super) struct Replace {
+    replacements: Punctuated<Replacement, Comma>,
+    _from:        keyword::from,
+    target:       Expr,
+}
+
+pub(super) struct Replacement {
+    path:  Path,
+    _eq:   syn::token::Eq,
+    value: Expr,
+}
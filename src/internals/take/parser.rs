@@ -0,0 +1,135 @@
+use syn::{parse::{Parse, ParseStream}, token, Member};
+use super::*;
+use crate::internals::retrieve::check_bindings;
+
+impl Parse for Take {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expression = input.parse()?;
+
+        let mut entries = Punctuated::new();
+        while !input.peek(keyword::from) {
+            entries.push_value(input.parse()?);
+            if input.peek(keyword::from) {break}
+            entries.push_punct(input.parse()?);
+        }
+        if entries.is_empty() {
+            return Err(input.error("expected at least one field to take"))
+        }
+        check_bindings(entries.iter().map(Entry::binding))?;
+
+        Ok(Self {
+            expression,
+            entries,
+            _from:  input.parse()?,
+            target: input.parse()?,
+        })
+    }
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let rename = if input.peek2(token::At) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let path = parse_place_path(input)?;
+        if rename.is_none() && matches!(path.last(), Member::Unnamed(_)) {
+            return Err(input.error("a tuple field needs a name to be bound like `x @ b.0`"))
+        }
+        Ok(Self { rename, path })
+    }
+}
+
+impl Parse for Replace {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut replacements = Punctuated::new();
+        while !input.peek(keyword::from) {
+            replacements.push_value(input.parse()?);
+            if input.peek(keyword::from) {break}
+            replacements.push_punct(input.parse()?);
+        }
+        if replacements.is_empty() {
+            return Err(input.error("expected at least one replacement like `b.c = new`"))
+        }
+
+        Ok(Self {
+            replacements,
+            _from:  input.parse()?,
+            target: input.parse()?,
+        })
+    }
+}
+
+impl Parse for Replacement {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            path:  parse_place_path(input)?,
+            _eq:   input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+/// A path of `retrieve!` grammar that always reaches a place to be borrowed by `&mut`
+fn parse_place_path(input: ParseStream) -> syn::Result<Path> {
+    let path = input.fork().parse::<Path>()?;
+    if path.is_nullable() {
+        return Err(input.error("`?` is not available here, because a value can't be left behind in `None`"))
+    }
+    if path.ends_with_method() {
+        return Err(input.error("a path here must end with a field or an index"))
+    }
+    input.parse()
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::{Take, Replace};
+
+    #[test]
+    fn parse_take() {
+        let case = parse2::<Take>(quote!(
+            a, x @ b.c, d[0] from self.state
+        )).unwrap();
+        assert!(case.expression.is_none());
+        assert_eq!(case.entries.len(), 3);
+
+        let case = parse2::<Take>(quote!(
+            => a, b.c from &mut s
+        )).unwrap();
+        assert!(case.expression.is_some());
+    }
+    #[test]
+    fn parse_take_errors() {
+        assert!(parse2::<Take>(quote!(
+            from s
+        )).is_err());
+        assert!(parse2::<Take>(quote!(
+            b?.c from s
+        )).is_err());
+        assert!(parse2::<Take>(quote!(
+            d.len() from s
+        )).is_err());
+        assert!(parse2::<Take>(quote!(
+            b.0 from s
+        )).is_err());
+        assert!(parse2::<Take>(quote!(
+            a, a @ b.c from s
+        )).is_err());
+    }
+    #[test]
+    fn parse_replace() {
+        let case = parse2::<Replace>(quote!(
+            b.c = String::new(), a = 0 from s
+        )).unwrap();
+        assert_eq!(case.replacements.len(), 2);
+
+        assert!(parse2::<Replace>(quote!(
+            b.c from s
+        )).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use proc_macro2::{TokenStream, Span};
+use quote::{quote, format_ident};
+use crate::internals::Build;
+use syn::Expr;
+use super::{Update, With, Operation, OperationKind};
+use crate::internals::retrieve::hoist_unless_place;
+
+/// The target is used directly when it's a place like `s` or `self.inner` ( it may be `&mut` itself ),
+/// otherwise evaluated once.
+impl Build for Update {
+    fn build(self) -> TokenStream {
+        let (statement, base) = hoist_unless_place(&self.target, "target");
+        let operations = self.operations.iter().map(|operation| operation.build(base.clone()));
+        quote!({
+            #statement
+            #( #operations )*
+        })
+    }
+}
+
+/// Modifies a moved ( or cloned ) value in a mutable local, and returns it.
+impl Build for With {
+    fn build(self) -> TokenStream {
+        let With(Update { target, operations, .. }) = self;
+
+        let value = format_ident!("__kozo_value", span = Span::mixed_site());
+        let initial = match &target {
+            Expr::Reference(reference) if reference.mutability.is_none() => quote!(
+                ::core::clone::Clone::clone(#target)
+            ),
+            _ => quote!(#target),
+        };
+        let operations = operations.iter().map(|operation| operation.build(quote!(#value)));
+        quote!({
+            let mut #value = #initial;
+            #( #operations )*
+            #value
+        })
+    }
+}
+
+impl Operation {
+    fn build(&self, base: TokenStream) -> TokenStream {
+        self.path.build_mut_place(base, |place| match &self.kind {
+            OperationKind::Assign { _eq, value } => quote!(#place #_eq #value;),
+            OperationKind::Compound { op, value } => quote!(#place #op #value;),
+            OperationKind::Call => quote!(#place;),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use crate::internals::{update::{Update, With}, Build};
+
+    #[test]
+    fn build_update() {
+        let case = parse2::<Update>(quote!(
+            s, b.c.d = 5, a += 1, d.push(3)
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                s.b.c.d = 5;
+                s.a += 1;
+                s.d.push(3);
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_with() {
+        let case = parse2::<With>(quote!(
+            s, b.c.d = 5, a = 9
+        )).unwrap(/* this parsing passed in parser::test */);
+        let value = quote::format_ident!("__kozo_value", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let mut #value = s;
+                #value.b.c.d = 5;
+                #value.a = 9;
+                #value
+            }).to_string()
+        );
+
+        let case = parse2::<With>(quote!(
+            &s, a = 9
+        )).unwrap(/* this parsing passed in parser::test */);
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let mut #value = ::core::clone::Clone::clone(&s);
+                #value.a = 9;
+                #value
+            }).to_string()
+        )
+    }
+    #[test]
+    fn build_update_nullable() {
+        let case = parse2::<Update>(quote!(
+            &mut s, b?.c?ok.d = 5, e[0]? += 1
+        )).unwrap(/* this parsing passed in parser::test */);
+        let target = quote::format_ident!("__kozo_target", span = proc_macro2::Span::mixed_site());
+        let v = quote::format_ident!("__kozo_v", span = proc_macro2::Span::mixed_site());
+        assert_eq!(
+            case.build().to_string(),
+            quote!({
+                let #target = &mut s;
+                if let ::core::option::Option::Some(#v) = #target.b.as_mut() {
+                    if let ::core::option::Option::Some(#v) = (*#v).c.as_mut().ok() {
+                        (*#v).d = 5;
+                    }
+                }
+                if let ::core::option::Option::Some(#v) = #target.e.get_mut(0) {
+                    (*#v) += 1;
+                }
+            }).to_string()
+        )
+    }
+}
@@ -0,0 +1,38 @@
+use syn::{punctuated::Punctuated, token::Comma, Expr, BinOp};
+use super::retrieve::Path;
+
+mod parser;
+mod builder;
+
+
+/// `s, b.c.d = 5, a += 1, d.push(3)`
+pub(super) struct Update {
+    target:     Expr,
+    _comma:     Comma,
+    operations: Punctuated<Operation, Comma>,
+}
+
+/// `s, b.c.d = 5, a = 9` : the same as `update!` but for a modified copy of `s`
+/// ( cloned when it's given by `&s` )
+pub(super) struct With(
+    Update
+);
+
+pub(super) struct Operation {
+    path: Path,
+    kind: OperationKind,
+}
+pub(super) enum OperationKind {
+    /// `b.c.d = 5`
+    Assign {
+        _eq:   syn::token::Eq,
+        value: Expr,
+    },
+    /// `a += 1`
+    Compound {
+        op:    BinOp,
+        value: Expr,
+    },
+    /// `d.push(3)` : the path ends with a method call
+    Call,
+}
@@ -0,0 +1,90 @@
+use syn::{parse::{Parse, ParseStream}, token, BinOp, Expr, ExprReference};
+use super::*;
+
+impl Parse for Update {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            target:     input.parse()?,
+            _comma:     input.parse()?,
+            operations: input.parse_terminated(Operation::parse)?,
+        })
+    }
+}
+
+impl Parse for With {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let update = input.parse::<Update>()?;
+        if let Expr::Reference(ExprReference { mutability: Some(mutability), .. }) = &update.target {
+            return Err(syn::Error::new_spanned(mutability,
+                "`with!` returns a modified copy, not modifying through `&mut` ( use `update!` for that )"
+            ))
+        }
+        Ok(Self(update))
+    }
+}
+
+impl Parse for Operation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<Path>()?;
+
+        let kind = if input.peek(token::Eq) && !input.peek(token::FatArrow) && !input.peek(token::EqEq) {
+            OperationKind::Assign {
+                _eq:   input.parse()?,
+                value: input.parse()?,
+            }
+        } else if input.is_empty() || input.peek(token::Comma) {
+            if !path.ends_with_method() {
+                return Err(input.error("expected `=`, compound assignment like `+=`, or a method call"))
+            }
+            OperationKind::Call
+        } else {
+            let op = input.parse::<BinOp>()?;
+            if !matches!(op,
+                BinOp::AddEq(_) | BinOp::SubEq(_) | BinOp::MulEq(_) | BinOp::DivEq(_) | BinOp::RemEq(_) |
+                BinOp::BitAndEq(_) | BinOp::BitOrEq(_) | BinOp::BitXorEq(_) | BinOp::ShlEq(_) | BinOp::ShrEq(_)
+            ) {
+                return Err(syn::Error::new_spanned(op, "expected `=` or compound assignment like `+=`"))
+            }
+            OperationKind::Compound {
+                op,
+                value: input.parse()?,
+            }
+        };
+
+        Ok(Self { path, kind })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+    use syn::parse2;
+    use super::{Update, With, OperationKind};
+
+    #[test]
+    fn parse_update() {
+        let case = parse2::<Update>(quote!(
+            self.inner, b.c.d = 5, a += 1, b?.f = F::Y, d.push(3),
+        )).unwrap();
+        assert_eq!(case.operations.len(), 4);
+        assert!(matches!(case.operations[0].kind, OperationKind::Assign { .. }));
+        assert!(matches!(case.operations[1].kind, OperationKind::Compound { .. }));
+        assert!(matches!(case.operations[3].kind, OperationKind::Call));
+    }
+    #[test]
+    fn parse_update_errors() {
+        assert!(parse2::<Update>(quote!(
+            s, b.c
+        )).is_err());
+        assert!(parse2::<Update>(quote!(
+            s, a + 1
+        )).is_err());
+        assert!(parse2::<With>(quote!(
+            &mut s, a = 9
+        )).is_err());
+        assert!(parse2::<With>(quote!(
+            &s, a = 9
+        )).is_ok());
+    }
+}
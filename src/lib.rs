@@ -43,6 +43,8 @@ mod internals;
 /// }
 /// ```
 /// Then, `define!` is **just a syntax sugar** of defining each named structs separately, so please pay attension to that **all structs declared in define!(); are visible** in its scope.
+/// 
+/// In addition, `define!` emits hidden field metadata of each struct ( as a `macro_rules!` named `__kozo_<StructName>` ), by which `retrieve!(.. from s as StructName)` or `new!` knows all the fields and their types. Like other `macro_rules!`, it's visible only **below the `define!`** in the same module ( or its child modules declared after that ), so those macros must be used there.
 #[proc_macro]
 pub fn define(content: TokenStream) -> TokenStream {
     match internals::define(content.into()) {
@@ -52,6 +54,42 @@ pub fn define(content: TokenStream) -> TokenStream {
 }
 
 
+/// `new!` builds a struct defined by `define!` without naming the nested types: a field given `{ field: value, .. }` is built as the struct of its type. The struct must be defined by `define!` visible at that point ( written above in the same module, or in an ancestor module above the child module ), otherwise it fails with "cannot find macro `__kozo_<StructName>`".
+/// 
+/// ```edition2021
+/// use kozo::{define, new};
+/// 
+/// define!(struct NestedStruct {
+///     a: Vec<u8>,
+///     b: struct B {
+///         c: struct C {
+///             d: u8,
+///             e: u8,
+///         },
+///         f: enum F {
+///             X,
+///             Y,
+///         },
+///     },
+/// });
+/// 
+/// fn main() {
+///     let e = 1;
+///     let s = new!(NestedStruct { a: vec![], b: { c: { d: 0, e }, f: F::X } });
+///     assert_eq!((s.b.c.d, s.b.c.e), (0, 1));
+///     assert!(matches!(s.b.f, F::X));
+/// }
+/// ```
+/// 
+/// A brace `{ }` is taken as a nested struct when it starts with `field:` or `field,`, otherwise it's a block expression. Unknown field or a field not of a struct type given `{ }` is a compile error pointing to the field.
+#[proc_macro]
+pub fn new(stream: TokenStream) -> TokenStream {
+    match internals::new(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
 /// `retrieve!` enables to simply get more than 1 value from a struct:
 /// 
 /// ```edition2021
@@ -79,10 +117,804 @@ pub fn define(content: TokenStream) -> TokenStream {
 ///     println!("{:?}", b.d);  // [1, 1, 0, 1, 0, 1, 1]
 /// }
 /// ```
+/// 
+/// Each entry can be a nested path like `b.d`, bound to its last field name or renamed by `name @ path`. `from &s` / `from &mut s` retrieves references instead of moving the fields out. Leading `=>`, `retrieve!` becomes an expression yielding the values as a tuple in the listed order ( or the value itself for single entry ):
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// # define!(struct Sample {
+/// #     a: u8,
+/// #     b: struct B {
+/// #         c: String,
+/// #         d: Vec<u8>,
+/// #     },
+/// # });
+/// fn first_and_message(s: &Sample) -> (&u8, &String) {
+///     retrieve!(=> first @ a, b.c from &s)
+/// }
+/// 
+/// fn main() {
+///     let s = Sample {
+///         a: 0,
+///         b: B {
+///             c: "I have an apple?".into(),
+///             d: vec![1, 1, 0, 1, 0, 1, 1],
+///         },
+///     };
+///     let (first, message) = first_and_message(&s);
+///     println!("{first}, {message}");  // 0, I have an apple?
+/// 
+///     retrieve!(apple @ b.c, d @ b.d from s);
+///     println!("{apple}");  // I have an apple?
+/// }
+/// ```
+/// 
+/// `as Type` retrieves by destructuring pattern. This is useful for enum variants, where `else { .. }` is required to bail out when the variant doesn't match. Fields of tuple variant are retrieved by index like `x @ 0`:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(enum F {
+///     X,
+///     Pair(u8, u8),
+///     Other {
+///         name: String,
+///         id: usize,
+///     },
+/// });
+/// 
+/// fn describe(f: &F) -> Result<String, &'static str> {
+///     retrieve!(name, id from f as F::Other else { return Err("not `Other`") });
+///     Ok(format!("{name}#{id}"))
+/// }
+/// 
+/// fn main() {
+///     let other = F::Other { name: "kozo".into(), id: 1 };
+///     assert_eq!(describe(&other), Ok("kozo#1".into()));
+///     assert_eq!(describe(&F::X), Err("not `Other`"));
+/// 
+///     let pair = F::Pair(0, 1);
+///     retrieve!(right @ 1 from pair as F::Pair else { unreachable!() });
+///     assert_eq!(right, 1);
+/// }
+/// ```
+/// 
+/// In paths, `field?` goes through an `Option` field and `field?ok` through a `Result` field. Then the retrieved value is wrapped in `Option`, or with trailing `else { .. }`, bails out when it's not found:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Sample {
+///     a: u8,
+///     b: Option<B>,
+///     c: Result<u8, String>,
+/// });
+/// define!(struct B {
+///     name: String,
+/// });
+/// 
+/// fn name_len(s: &Sample) -> Option<usize> {
+///     retrieve!(name @ b?.name from &s else { return None });
+///     Some(name.len())
+/// }
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: Some(B { name: "kozo".into() }), c: Err("not loaded".into()) };
+///     assert_eq!(name_len(&s), Some(4));
+/// 
+///     retrieve!(name @ b?.name, c @ c?ok from s);
+///     assert_eq!(name, Some("kozo".to_string()));
+///     assert_eq!(c, None);
+/// }
+/// ```
+/// 
+/// Paths also take index steps like `d[0]`, `d[..3]` or `by_name["key"]`. A slice by range is retrieved as borrowed, and `[index]?` retrieves by `.get(index)` into `Option` instead of panicking:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// use std::collections::HashMap;
+/// 
+/// define!(struct Sample {
+///     d: Vec<u8>,
+///     by_name: HashMap<String, u8>,
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { d: vec![1, 2, 3, 4], by_name: HashMap::from([("key".to_string(), 42)]) };
+///     retrieve!(first @ d[0], head @ d[..3], tenth @ d[9]?, v @ by_name["key"] from s);
+/// 
+///     assert_eq!(first, 1);
+///     assert_eq!(head, &[1, 2, 3]);
+///     assert_eq!(tenth, None);
+///     assert_eq!(v, 42);
+/// }
+/// ```
+/// 
+/// Method calls are also available as steps, and the default binding name is the method name. What a method returns is bound as it is, and `method()?` / `method()?ok` goes through the returned `Option` / `Result`. Entries are evaluated from left to right, and a method call shared by the paths of some entries before any `?` ( or a target that is not a place ) is evaluated only once, so an entry can't take what it returns by value without `clone` / `copy`:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct User {
+///     name: String,
+///     id:   u32,
+/// });
+/// define!(struct Sample {
+///     d:     Vec<u8>,
+///     users: Vec<User>,
+/// });
+/// impl Sample {
+///     fn user(&self) -> &User {
+///         &self.users[0]
+///     }
+/// }
+/// 
+/// fn main() {
+///     let s = Sample { d: vec![1, 2, 3], users: vec![User { name: "kozo".into(), id: 42 }] };
+///     retrieve!(len @ d.len(), name @ user().name, id @ user().id copy, last @ d.last()?, second @ users.get(1)?.name from &s);
+/// 
+///     assert_eq!(len, 3);
+///     assert_eq!((name.as_str(), id), ("kozo", 42));
+///     assert_eq!(last, Some(&3));
+///     assert_eq!(second, None);
+/// }
+/// ```
+/// 
+/// Each entry can be followed by `: Type` ascription and one of `as Type`, `into Type`, `clone` or `=> expression` ( referring the value by the last field name ) to convert the value on binding:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: Vec<u8>,
+///         d: Vec<u8>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: B { c: vec![1, 1, 0], d: vec![1, 2, 3] } };
+///     retrieve!(a as u64, c @ b.c clone, len @ b.c => c.len(), d @ b.d: Vec<u16> => d.into_iter().map(u16::from).collect() from s);
+/// 
+///     assert_eq!(a, 0u64);
+///     assert_eq!((c, len), (vec![1, 1, 0], 3));
+///     assert_eq!(d, vec![1u16, 2, 3]);
+/// }
+/// ```
+/// 
+/// `= default` after the path ( and ascription ) unwraps an `Option` value, evaluating `default` only when it's `None`. When the target is borrowed, the value is `Cow` of the field, or owned one with `clone` / `copy`. With `&mut` target, the default is inserted into the `Option` field and the value is `&mut` to its content:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// use std::borrow::Cow;
+/// 
+/// define!(struct Config {
+///     timeout: Option<u64>,
+///     name:    Option<String>,
+///     retry:   Option<Retry>,
+/// });
+/// define!(struct Retry {
+///     count: u8,
+/// });
+/// 
+/// fn main() {
+///     let mut cfg = Config { timeout: None, name: Some("kozo".into()), retry: None };
+/// 
+///     retrieve!(name: Cow<str> = "anonymous".into(), timeout = 30 copy, count @ retry?.count = 3 copy from &cfg);
+///     assert_eq!((name, timeout, count), (Cow::Borrowed("kozo"), 30, 3));
+/// 
+///     retrieve!(timeout = 30 from &mut cfg);
+///     *timeout += 1;
+///     assert_eq!(cfg.timeout, Some(31));
+/// }
+/// ```
+/// 
+/// `clone` / `copy` entries, or all entries after leading `clone:`, are retrieved through a borrow and cloned / copied, so the target is still available after that, even when it's a shared pointer like `Arc<Sample>`:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// use std::sync::Arc;
+/// 
+/// define!(struct Config {
+///     name: String,
+///     retry: struct Retry {
+///         count: u8,
+///         backoff: Option<u64>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let config = Arc::new(Config {
+///         name: "kozo".into(),
+///         retry: Retry { count: 3, backoff: Some(100) },
+///     });
+/// 
+///     retrieve!(clone: name, count @ retry.count, backoff @ retry.backoff? from config);
+///     assert_eq!((name, count, backoff), ("kozo".to_string(), 3, Some(100)));
+/// 
+///     retrieve!(count @ retry.count copy from config);
+///     assert_eq!(config.retry.count, count);
+/// }
+/// ```
+/// 
+/// This holds with `as Variant` too:
+/// 
+/// ```edition2021
+/// # use kozo::retrieve;
+/// enum F {
+///     X,
+///     Other { name: String, id: u64 },
+/// }
+/// 
+/// fn main() {
+///     let f = F::Other { name: "kozo".into(), id: 1 };
+/// 
+///     retrieve!(id copy from f as F::Other else { return });
+///     retrieve!(name clone from f as F::Other else { return });
+///     assert_eq!((name.as_str(), id), ("kozo", 1));
+/// 
+///     let _ = f;
+/// #   let _ = F::X;
+/// }
+/// ```
+/// 
+/// After leading `assign:`, values are assigned to existing variables instead of binding by `let`. Each entry is assigned to the variable of its binding name, the place given by `path => place`, or compoundly by `place += path` ( and other compound assignment operators ):
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Item {
+///     name: String,
+///     detail: struct Detail {
+///         count: usize,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let items = vec![
+///         Item { name: "apple".into(), detail: Detail { count: 2 } },
+///         Item { name: "banana".into(), detail: Detail { count: 3 } },
+///     ];
+/// 
+///     let (mut name, mut last_count, mut total) = (String::new(), 0, 0);
+///     for item in items {
+///         retrieve!(assign: name, detail.count => last_count, total += detail.count from item);
+///     }
+///     assert_eq!((name.as_str(), last_count, total), ("banana", 3, 5));
+/// }
+/// ```
+/// 
+/// Multiple targets can be given separated by `;`. Leading `=>`, `clone:` or `assign:` is applied to all of them:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Request {
+///     path: String,
+///     body: Vec<u8>,
+/// });
+/// define!(struct Config {
+///     timeout: u64,
+/// });
+/// 
+/// fn main() {
+///     let request = Request { path: "/".into(), body: vec![] };
+///     let config = Config { timeout: 30 };
+/// 
+///     retrieve!(path, body from request; timeout from &config);
+///     assert_eq!((path.as_str(), body.len(), *timeout), ("/", 0, 30));
+/// }
+/// ```
+/// 
+/// Retrieving the same name more than once ( even from different targets ) is a compile error.
+/// 
+/// For a struct defined by `define!`, `..` retrieves all its fields ( or `.. except a, b` all but `a` and `b` ). Because `retrieve!` can't see the type of target, this needs it by `as Type`, which must name a struct defined by `define!` visible at that point ( the same as `new!` ):
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Sample {
+///     a: u8,
+///     b: String,
+///     c: Vec<u8>,
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: "kozo".into(), c: vec![1, 2, 3] };
+/// 
+///     retrieve!(.. except c from &s as Sample);
+///     assert_eq!((*a, b.as_str()), (0, "kozo"));
+/// 
+///     let (a, b, c) = retrieve!(=> .. from s as Sample);
+///     assert_eq!((a, b.len(), c.len()), (0, 4, 3));
+/// }
+/// ```
+/// 
+/// A name in `except` which is not a field of the type is a compile error.
+/// 
+/// `exhaustive` before entries destructures the target without `..`, so the entries must cover all fields of `as Type` ( a struct defined by `define!` visible at that point ), or it's a compile error. This makes adding a field to the struct break every exhaustive retrieval from it. A field of another struct defined by `define!` can be exhaustively destructured in nested `{ }`:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///         d: Vec<u8>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: B { c: "kozo".into(), d: vec![1, 2] } };
+///     retrieve!(exhaustive a, b { c, d } from &s as Sample);
+///     assert_eq!((a, c.as_str(), d.len()), (&0, "kozo", 2));
+/// }
+/// ```
+/// 
+/// ```compile_fail
+/// # use kozo::{define, retrieve};
+/// define!(struct Sample {
+///     a: u8,
+///     b: u8,
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: 1 };
+///     retrieve!(exhaustive a from s as Sample); // missing `b`
+/// }
+/// ```
+/// 
+/// `each` before entries iterates over the target ( any `IntoIterator` ) once and collects the values of each entry. The collection is `Vec` by default, or the one given by `: Type`:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// use std::collections::HashSet;
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let samples = vec![
+///         Sample { a: 1, b: B { c: "one".into() } },
+///         Sample { a: 1, b: B { c: "another one".into() } },
+///     ];
+/// 
+///     retrieve!(clone: each a: HashSet<u8>, b.c from samples);
+///     assert_eq!(a, HashSet::from([1]));
+///     assert_eq!(c, vec!["one", "another one"]);
+/// 
+///     retrieve!(each lens @ b.c => c.len() from &samples);
+///     assert_eq!(lens, vec![3, 11]);
+/// }
+/// ```
+/// 
+/// Trailing `into Type` after the last target makes `retrieve!` an expression building `Type` from the values, each as the field of its binding name:
+/// 
+/// ```edition2021
+/// # use kozo::{define, retrieve};
+/// define!(struct Request {
+///     id:   u64,
+///     user: struct User {
+///         name:  String,
+///         email: String,
+///     },
+///     body: String,
+/// });
+/// 
+/// #[derive(Debug, PartialEq)]
+/// struct Summary {
+///     id:     u64,
+///     author: String,
+/// }
+/// 
+/// fn main() {
+///     let req = Request { id: 1, user: User { name: "kozo".into(), email: "kozo@example.com".into() }, body: "".into() };
+///     let summary = retrieve!(id, author @ user.name from req into Summary);
+///     assert_eq!(summary, Summary { id: 1, author: "kozo".into() });
+/// }
+/// ```
 #[proc_macro]
 pub fn retrieve(stream: TokenStream) -> TokenStream {
     match internals::retrieve(stream.into()) {
         Err(error) => error.into_compile_error(),
         Ok(result) => result,
     }.into()
-}
\ No newline at end of file
+}
+
+/// `assemble!` is the inverse of `retrieve!`, building a struct defined by `define!` from the local variables of the same names. Fields are given by paths like `retrieve!` ( and renamed by `local @ path` ) followed by `into Type`, or in a literal form with nested `{ }`. In the literal form, `..` at the end of a `{ }` fills the other fields of that struct by `Default::default()`, like struct update syntax. In the list form, trailing `..` does so for **every** struct being built, so all of them need `Default`:
+/// 
+/// ```edition2021
+/// use kozo::{define, retrieve, assemble};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///         d: Vec<u8>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: B { c: "kozo".into(), d: vec![1] } };
+///     retrieve!(a, name @ b.c, b.d from s);
+///     let s = assemble!(a, name @ b.c, b.d into Sample);
+///     assert_eq!((s.a, s.b.c.as_str(), s.b.d.len()), (0, "kozo", 1));
+/// 
+///     let (a, c, d) = (1, String::from("assembled"), vec![]);
+///     let s = assemble!(Sample { a, b: { c, d } });
+///     assert_eq!(s.b.c, "assembled");
+/// 
+///     let c = String::from("partial");
+///     let s = assemble!(Sample { a, b: { c, .. } }); // only `B` needs `Default`
+///     assert_eq!((s.b.c.as_str(), s.b.d.len()), ("partial", 0));
+/// }
+/// 
+/// impl Default for B {
+///     fn default() -> Self {
+///         B { c: String::new(), d: Vec::new() }
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn assemble(stream: TokenStream) -> TokenStream {
+    match internals::assemble(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `update!` applies assignments, compound assignments and method calls to the paths of target ( a mutable place, or an expression yielding `&mut` evaluated once ). Paths are the same as `retrieve!`, and an operation through `field?` ( `field?ok`, `[index]?` ) is applied only when the value exists:
+/// 
+/// ```edition2021
+/// use kozo::{define, update};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///         e: Option<u8>,
+///     },
+///     d: Vec<u8>,
+///     f: Option<u8>,
+/// });
+/// 
+/// fn main() {
+///     let mut s = Sample { a: 0, b: B { c: "kozo".into(), e: Some(1) }, d: vec![], f: None };
+///     update!(s, a += 1, b.c = "updated".into(), b.e? *= 10, d.push(3), f? = 100);
+/// 
+///     assert_eq!((s.a, s.b.c.as_str(), s.b.e), (1, "updated", Some(10)));
+///     assert_eq!((s.d, s.f), (vec![3], None));
+/// }
+/// ```
+#[proc_macro]
+pub fn update(stream: TokenStream) -> TokenStream {
+    match internals::update(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `with!` is the functional version of `update!`, returning a modified value. The target is moved ( so it doesn't need `Clone` ), or cloned when it's given as `&s`. `&mut s` is a compile error, because modifying through it is what `update!` does:
+/// 
+/// ```edition2021
+/// use kozo::{define, with};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: B { c: "kozo".into() } };
+///     let s2 = with!(s, a = 9, b.c = "modified".into());
+///     assert_eq!((s2.a, s2.b.c.as_str()), (9, "modified"));
+/// }
+/// ```
+#[proc_macro]
+pub fn with(stream: TokenStream) -> TokenStream {
+    match internals::with(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `take!` moves values out of a struct only mutably accessible ( e.g. in `Drop::drop` ), leaving `Default` values behind by `std::mem::take`. Like `retrieve!`, the values are bound to local variables, or yielded with leading `=>`:
+/// 
+/// ```edition2021
+/// use kozo::{define, take};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///         d: Vec<u8>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let mut s = Sample { a: 1, b: B { c: "kozo".into(), d: vec![1, 2] } };
+///     take!(a, x @ b.c from &mut s);
+///     assert_eq!((a, x.as_str()), (1, "kozo"));
+/// 
+///     let d = take!(=> b.d from s);
+///     assert_eq!(d, [1, 2]);
+///     assert_eq!((s.a, s.b.c.as_str(), s.b.d.len()), (0, "", 0));
+/// }
+/// ```
+#[proc_macro]
+pub fn take(stream: TokenStream) -> TokenStream {
+    match internals::take(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `replace!` puts new values into a struct by `std::mem::replace`, and returns the old ones ( as a tuple for more than 1 ):
+/// 
+/// ```edition2021
+/// use kozo::{define, replace};
+/// 
+/// define!(struct Machine {
+///     state: enum State {
+///         Idle,
+///         Running(u32),
+///     },
+///     count: usize,
+/// });
+/// 
+/// fn main() {
+///     let mut m = Machine { state: State::Running(3), count: 1 };
+///     let previous = replace!(state = State::Idle from m);
+///     assert!(matches!(previous, State::Running(3)));
+/// 
+///     let (state, count) = replace!(state = State::Running(0), count = 0 from &mut m);
+///     assert!(matches!(state, State::Idle));
+///     assert_eq!(count, 1);
+/// }
+/// ```
+#[proc_macro]
+pub fn replace(stream: TokenStream) -> TokenStream {
+    match internals::replace(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `copy_fields!` assigns values of fields from a struct to another, maybe of different type, by `b.c => x.c` or the same path. They are moved by default, or `clone:` / `into:` / `clone into:` at the beginning converts them:
+/// 
+/// ```edition2021
+/// use kozo::{define, copy_fields};
+/// 
+/// define!(struct Patch {
+///     name: String,
+///     profile: struct PatchProfile {
+///         age: u8,
+///     },
+/// });
+/// define!(struct User {
+///     id: usize,
+///     name: String,
+///     detail: struct Detail {
+///         age: u32,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let patch = Patch { name: "kozo".into(), profile: PatchProfile { age: 20 } };
+///     let mut user = User { id: 1, name: String::new(), detail: Detail { age: 0 } };
+/// 
+///     copy_fields!(into: name, profile.age => detail.age from patch to user);
+///     assert_eq!((user.id, user.name.as_str(), user.detail.age), (1, "kozo", 20));
+/// }
+/// ```
+#[proc_macro]
+pub fn copy_fields(stream: TokenStream) -> TokenStream {
+    match internals::copy_fields(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `assert_fields!` asserts the values of some fields by `==`, `!=` or `matches` ( a pattern ), in the same path syntax as `retrieve!`. It checks all of them and then panics with every failure, showing the paths and `Debug` outputs ( an index out of bounds is also a failure ):
+/// 
+/// ```edition2021
+/// use kozo::{define, assert_fields};
+/// 
+/// #[derive(Debug)]
+/// enum F {
+///     X,
+///     Other { id: usize },
+/// }
+/// 
+/// define!(struct Sample {
+///     a: Vec<u8>,
+///     b: struct B {
+///         c: u8,
+///         f: F,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: vec![1], b: B { c: 0, f: F::Other { id: 3 } } };
+///     assert_fields!(s, a == vec![1], b.c != 1, a.len() == 1, b.f matches F::Other { id: 1.. });
+/// 
+///     let result = std::panic::catch_unwind(|| assert_fields!(s, a[0] == 2, b.c == 0, b.f matches F::X));
+///     assert!(result.is_err());
+/// 
+///     let result = std::panic::catch_unwind(|| assert_fields!(s, a[5] == 1, b.c == 1));
+///     let message = result.unwrap_err().downcast::<String>().unwrap();
+///     assert_eq!(*message, "assertion failed for 2 of 2 fields of `s`:\n  a[5]: index out of bounds\n  b.c: expected 1, found 0");
+/// }
+/// ```
+#[proc_macro]
+pub fn assert_fields(stream: TokenStream) -> TokenStream {
+    match internals::assert_fields(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `matches_shape!` tells whether some fields match the patterns, in nested `{ .. }` of field paths instead of type-qualified patterns. Fields not listed are not checked, and the bindings in the patterns are available in trailing `if` guard ( as references ):
+/// 
+/// ```edition2021
+/// use kozo::{define, matches_shape};
+/// 
+/// define!(struct Sample {
+///     a: Vec<u8>,
+///     b: struct B {
+///         c: u8,
+///         f: enum F {
+///             X,
+///             Other { name: String, id: usize },
+///         },
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: vec![1, 2], b: B { c: 0, f: F::Other { name: "kozo".into(), id: 3 } } };
+/// 
+///     assert!(matches_shape!(s, { a: [1, ..], b: { f: F::Other { id: 1.., .. }, .. } }));
+///     assert!(matches_shape!(s, { b.c: 0 | 1, b.f: F::Other { id, .. } } if *id % 2 == 1));
+///     assert!(!matches_shape!(s, { a.len(): 0, b: { f: F::X } }));
+/// }
+/// ```
+#[proc_macro]
+pub fn matches_shape(stream: TokenStream) -> TokenStream {
+    match internals::matches_shape(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `dbg_fields!` prints some fields with `[file:line:column]` to stderr like `dbg!`, and yields the target. So, as `dbg!`, it moves the target unless given by a reference:
+/// 
+/// ```edition2021
+/// use kozo::{define, dbg_fields};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: Option<String>,
+///         d: Vec<u8>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: B { c: Some("kozo".into()), d: vec![1, 2] } };
+///     dbg_fields!(&s, a, b.c, b.d.len());
+/// 
+///     let s = dbg_fields!(s, b.c?.len(), b.d[0]);
+///     assert_eq!(s.a, 0);
+/// }
+/// ```
+#[proc_macro]
+pub fn dbg_fields(stream: TokenStream) -> TokenStream {
+    match internals::dbg_fields(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `trace_fields!` does the same as `dbg_fields!` through the given logging macro like `log::debug!` ( which is expected to record the location ):
+/// 
+/// ```edition2021
+/// use kozo::{define, trace_fields};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let s = Sample { a: 0, b: B { c: "kozo".into() } };
+///     trace_fields!(println!, &s, a, b.c);
+/// }
+/// ```
+#[proc_macro]
+pub fn trace_fields(stream: TokenStream) -> TokenStream {
+    match internals::trace_fields(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `path!(Type.a.b)` makes a lens to the nested field `a.b` of `Type`: a zero-sized `Copy` value with `get(&Type) -> &T`, `get_mut(&mut Type) -> &mut T` and `set(&mut Type, T)`, where `T` is the type of the field. `Type` and the structs on the way must be defined by `define!` visible at that point ( the same as `new!` ), from whose metadata the types of fields are known:
+/// 
+/// ```edition2021
+/// use kozo::{define, path};
+/// 
+/// define!(struct Sample {
+///     a: u8,
+///     b: struct B {
+///         c: String,
+///         d: Vec<u8>,
+///     },
+/// });
+/// 
+/// fn main() {
+///     let mut s = Sample { a: 1, b: B { c: "kozo".into(), d: vec![] } };
+/// 
+///     let c = path!(Sample.b.c);
+///     assert_eq!(c.get(&s), "kozo");
+///     c.set(&mut s, "lens".into());
+///     path!(Sample.b.d).get_mut(&mut s).push(1);
+/// 
+///     assert_eq!(s.b.c, "lens");
+///     assert_eq!(s.b.d, [1]);
+/// }
+/// ```
+/// 
+/// The type of a lens is defined where `path!` is used, so it can't be named, and it implements no trait other than `Clone` / `Copy`.
+#[proc_macro]
+pub fn path(stream: TokenStream) -> TokenStream {
+    match internals::path(stream.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}
+
+/// `#[retrieving(..)]` injects `retrieve!(..)` at the top of the function body, with the same syntax except leading `=>` and trailing `into ..`. Without `from ..`, it retrieves from the first parameter ( borrowed when it's a reference ). So the parameter is moved by default, and stays usable with `clone:` or `from &param`:
+/// 
+/// ```edition2021
+/// use kozo::{define, retrieving};
+/// 
+/// define!(struct Request {
+///     id:   u64,
+///     user: struct User {
+///         name: String,
+///     },
+/// });
+/// 
+/// #[retrieving(id, name @ user.name)]
+/// fn handle(req: Request) -> String {
+///     format!("{id}: {name}")
+/// }
+/// 
+/// #[retrieving(name @ user.name)]
+/// fn name_len(req: &Request) -> usize {
+///     name.len()
+/// }
+/// 
+/// #[retrieving(id from &req)]
+/// fn id_and_request(req: Request) -> (u64, Request) {
+///     (*id, req)
+/// }
+/// 
+/// fn main() {
+///     let req = || Request { id: 1, user: User { name: "kozo".into() } };
+///     assert_eq!(handle(req()), "1: kozo");
+///     assert_eq!(name_len(&req()), 4);
+///     assert_eq!(id_and_request(req()).0, 1);
+/// }
+/// ```
+/// 
+/// ( It's not named `retrieve` because an attribute macro can't share the name with `retrieve!` )
+#[proc_macro_attribute]
+pub fn retrieving(args: TokenStream, item: TokenStream) -> TokenStream {
+    match internals::retrieving(args.into(), item.into()) {
+        Err(error) => error.into_compile_error(),
+        Ok(result) => result,
+    }.into()
+}